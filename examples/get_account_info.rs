@@ -2,7 +2,7 @@ use solana_jsonrpc_client::{methods, JsonRpcClient};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com");
+    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com").with_cassette_from_env()?;
 
     let request = methods::account::RpcGetAccountInfoRequest {
         pubkey: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
@@ -17,12 +17,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let response = client.call(request).await?;
     
     println!("Account Info Response:");
-    println!("  Context Slot: {}", response.context.slot);
+    if let Some(slot) = response.slot() {
+        println!("  Context Slot: {}", slot);
+    }
     if let Some(account) = response.value {
-        println!("  Lamports: {}", account.lamports);
+        println!("  Lamports: {}", account.lamports.get());
         println!("  Owner: {}", account.owner);
         println!("  Executable: {}", account.executable);
         println!("  Rent Epoch: {}", account.rent_epoch);
+        match account.data_bytes() {
+            Ok(data) => println!("  Data: {} bytes", data.len()),
+            Err(err) => println!("  Data: {}", err),
+        }
     } else {
         println!("  Account not found");
     }
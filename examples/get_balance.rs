@@ -2,7 +2,7 @@ use solana_jsonrpc_client::{methods, JsonRpcClient};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com");
+    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com").with_cassette_from_env()?;
 
     let request = methods::account::RpcGetBalanceRequest {
         pubkey: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
@@ -14,9 +14,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let response = client.call(request).await?;
     
-    println!("Balance: {} lamports", response.value);
-    println!("Context Slot: {}", response.context.slot);
-    println!("Balance in SOL: {:.9}", response.value as f64 / 1_000_000_000.0);
+    println!("Balance: {} lamports", response.value.get());
+    if let Some(slot) = response.slot() {
+        println!("Context Slot: {}", slot);
+    }
+    println!("Balance in SOL: {}", response.value);
 
     Ok(())
 }
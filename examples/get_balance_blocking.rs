@@ -0,0 +1,22 @@
+use solana_jsonrpc_client::{blocking::JsonRpcClient, methods};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com");
+
+    let request = methods::account::RpcGetBalanceRequest {
+        pubkey: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
+        config: Some(methods::account::BalanceConfig {
+            commitment: Some(solana_jsonrpc_client::types::Commitment::Finalized),
+            min_context_slot: None,
+        }),
+    };
+
+    let response = client.call(request)?;
+
+    println!("Balance: {} lamports", response.value.get());
+    if let Some(slot) = response.slot() {
+        println!("Context Slot: {}", slot);
+    }
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use solana_jsonrpc_client::{methods, JsonRpcClient};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com");
+    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com").with_cassette_from_env()?;
 
     // First, get the current slot
     let slot_request = methods::block::RpcGetSlotRequest {
@@ -10,10 +10,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         min_context_slot: None,
     };
     let slot_response = client.call(slot_request).await?;
-    let current_slot = match slot_response {
-        methods::block::RpcGetSlotResponse::Simple(slot) => slot,
-        methods::block::RpcGetSlotResponse::WithContext { value, .. } => value,
-    };
+    let current_slot = slot_response.into_value();
     println!("Current slot: {}", current_slot);
 
     // Get a recent block
@@ -31,16 +28,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let response = client.call(request).await?;
     
     println!("Block Response:");
-    let (block, context_slot) = match &response {
-        methods::block::RpcGetBlockResponse::Simple(block) => (block.as_ref(), None),
-        methods::block::RpcGetBlockResponse::WithContext { value, context } => {
-            (value.as_ref(), Some(context.slot))
-        }
-    };
-    if let Some(slot) = context_slot {
+    if let Some(slot) = response.slot() {
         println!("  Context Slot: {}", slot);
     }
-    if let Some(block) = block {
+    if let Some(block) = response.value() {
         if let Some(ref blockhash) = block.blockhash {
             println!("  Blockhash: {}", blockhash);
         }
@@ -2,7 +2,7 @@ use solana_jsonrpc_client::{methods, JsonRpcClient};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com");
+    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com").with_cassette_from_env()?;
 
     // Example: Get accounts for the System Program
     let program_id = "11111111111111111111111111111111";
@@ -22,13 +22,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let response = client.call(request).await?;
     
     println!("Program Accounts Response:");
-    println!("  Context Slot: {}", response.context.slot);
+    if let Some(slot) = response.slot() {
+        println!("  Context Slot: {}", slot);
+    }
     println!("  Number of accounts: {}", response.value.len());
     
     // Print first few accounts
     for (i, account) in response.value.iter().take(5).enumerate() {
         println!("  Account {}: {}", i + 1, account.pubkey);
-        println!("    Lamports: {}", account.account.lamports);
+        println!("    Lamports: {}", account.account.lamports.get());
     }
 
     Ok(())
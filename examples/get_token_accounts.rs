@@ -2,7 +2,7 @@ use solana_jsonrpc_client::{methods, JsonRpcClient};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com");
+    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com").with_cassette_from_env()?;
 
     // Example: Get token accounts for a wallet (replace with a real wallet address)
     let owner = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
@@ -21,11 +21,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let response = client.call(request).await?;
     
     println!("Token Accounts Response:");
-    println!("  Context Slot: {}", response.context.slot);
+    if let Some(slot) = response.slot() {
+        println!("  Context Slot: {}", slot);
+    }
     println!("  Number of token accounts: {}", response.value.len());
     
     for (i, account) in response.value.iter().take(5).enumerate() {
         println!("  Token Account {}: {}", i + 1, account.pubkey);
+        if let Ok(token) = account.token_account() {
+            println!("    Mint: {}", token.mint);
+            println!("    Amount: {}", token.token_amount.ui_amount_string);
+            println!("    State: {:?}", token.state);
+        }
     }
 
     Ok(())
@@ -2,13 +2,13 @@ use solana_jsonrpc_client::{methods, JsonRpcClient};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com");
+    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com").with_cassette_from_env()?;
 
     // Example transaction signature (replace with a real one)
     let signature = "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
 
     let request = methods::transaction::RpcGetTransactionRequest {
-        signature: signature.to_string(),
+        signature: signature.parse()?,
         config: Some(methods::transaction::TransactionConfig {
             encoding: Some(solana_jsonrpc_client::types::TransactionEncoding::Json),
             commitment: Some(solana_jsonrpc_client::types::Commitment::Finalized),
@@ -19,7 +19,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let response = client.call(request).await?;
     
     println!("Transaction Response:");
-    println!("  Context Slot: {}", response.context.slot);
+    if let Some(slot) = response.slot() {
+        println!("  Context Slot: {}", slot);
+    }
     if let Some(transaction) = response.value {
         if let Some(ref slot) = transaction.slot {
             println!("  Slot: {}", slot);
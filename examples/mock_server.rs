@@ -0,0 +1,57 @@
+use solana_jsonrpc_client::test_utils::{Fault, MockServer};
+use solana_jsonrpc_client::{methods, JsonRpcError};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start().await;
+    let client = server.client();
+
+    let pubkey = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    server.ledger().set_balance(pubkey, 2_500_000_000);
+    server.ledger().advance_slots(10);
+
+    let balance = client
+        .call(methods::account::RpcGetBalanceRequest {
+            pubkey: pubkey.to_string(),
+            config: None,
+        })
+        .await?;
+    println!("Balance: {} lamports at slot {}", balance.value.get(), balance.slot().unwrap_or_default());
+
+    let blockhash = client
+        .call(methods::block::RpcGetLatestBlockhashRequest {
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await?;
+    println!("Latest blockhash: {}", blockhash.value.blockhash);
+
+    // Fail the next call with a node-behind error, then recover.
+    server.inject_fault_times(
+        "getBalance",
+        Fault::rpc(-32005, "Node is behind by 42 slots"),
+        1,
+    );
+    let request = methods::account::RpcGetBalanceRequest {
+        pubkey: pubkey.to_string(),
+        config: None,
+    };
+    match client.call(request).await {
+        Err(JsonRpcError::Rpc { code, message }) => println!("Injected error {}: {}", code, message),
+        other => println!("Unexpected result: {:?}", other.map(|response| response.value)),
+    }
+
+    server.set_latency(Duration::from_millis(50));
+    let balance = client
+        .call(methods::account::RpcGetBalanceRequest {
+            pubkey: pubkey.to_string(),
+            config: None,
+        })
+        .await?;
+    println!("Balance after recovery: {} lamports", balance.value.get());
+
+    println!("getBalance calls served: {}", server.call_count("getBalance"));
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use futures::StreamExt;
+use solana_jsonrpc_client::types::Lamports;
+use solana_jsonrpc_client::{methods, JsonRpcClient};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = JsonRpcClient::connect("https://api.mainnet-beta.solana.com").with_cassette_from_env()?;
+
+    // Example: Stream the mint accounts of the SPL Token program
+    let program_id = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    let request = methods::program::RpcGetProgramAccountsRequest {
+        program_id: program_id.to_string(),
+        config: Some(methods::program::ProgramAccountsConfig {
+            commitment: Some(solana_jsonrpc_client::types::Commitment::Finalized),
+            encoding: Some(solana_jsonrpc_client::types::Encoding::Base64),
+            filters: Some(vec![methods::program::ProgramAccountFilter::DataSize(82)]),
+            min_context_slot: None,
+            data_slice: Some(methods::program::DataSliceConfig { offset: 0, length: 0 }),
+            with_context: None,
+        }),
+    };
+
+    let accounts = client.stream_program_accounts(request).await?;
+    futures::pin_mut!(accounts);
+
+    let mut count = 0;
+    let mut lamports = Lamports::ZERO;
+    while let Some(account) = accounts.next().await {
+        let account = account?;
+        count += 1;
+        lamports = lamports.checked_add(account.account.lamports).ok_or("lamport total overflowed")?;
+    }
+
+    println!("Program Accounts Stream:");
+    println!("  Number of accounts: {}", count);
+    println!("  Total balance: {}", lamports);
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+// Decodes Borsh data into JSON following an IDL's type definitions.
+//
+// Integers wider than 64 bits become strings, pubkeys become base58, and
+// enums follow serde's external tagging: unit variants are strings, others
+// objects keyed by the variant name.
+
+use super::idl::{Idl, IdlError, IdlFields, IdlType, IdlTypeDefTy};
+use serde_json::{json, Map, Value};
+
+// Guards against self-referential type definitions.
+const MAX_DEPTH: usize = 64;
+
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(data: &'a [u8]) -> Self {
+        Self { data, depth: 0 }
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], IdlError> {
+        let bytes = self.take_slice(N)?;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn take_slice(&mut self, len: usize) -> Result<&'a [u8], IdlError> {
+        if self.data.len() < len {
+            return Err(IdlError::UnexpectedEnd);
+        }
+        let (head, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(head)
+    }
+
+    fn take_len(&mut self) -> Result<usize, IdlError> {
+        Ok(u32::from_le_bytes(self.take()?) as usize)
+    }
+
+    pub(crate) fn decode(&mut self, idl: &Idl, ty: &IdlType) -> Result<Value, IdlError> {
+        if self.depth >= MAX_DEPTH {
+            return Err(IdlError::InvalidData("type nesting is too deep".to_string()));
+        }
+        self.depth += 1;
+        let value = self.decode_inner(idl, ty);
+        self.depth -= 1;
+        value
+    }
+
+    fn decode_inner(&mut self, idl: &Idl, ty: &IdlType) -> Result<Value, IdlError> {
+        Ok(match ty {
+            IdlType::Bool => match self.take::<1>()?[0] {
+                0 => Value::Bool(false),
+                1 => Value::Bool(true),
+                other => return Err(IdlError::InvalidData(format!("invalid bool {}", other))),
+            },
+            IdlType::U8 => u8::from_le_bytes(self.take()?).into(),
+            IdlType::I8 => i8::from_le_bytes(self.take()?).into(),
+            IdlType::U16 => u16::from_le_bytes(self.take()?).into(),
+            IdlType::I16 => i16::from_le_bytes(self.take()?).into(),
+            IdlType::U32 => u32::from_le_bytes(self.take()?).into(),
+            IdlType::I32 => i32::from_le_bytes(self.take()?).into(),
+            IdlType::U64 => u64::from_le_bytes(self.take()?).into(),
+            IdlType::I64 => i64::from_le_bytes(self.take()?).into(),
+            IdlType::F32 => json!(f32::from_le_bytes(self.take()?)),
+            IdlType::F64 => json!(f64::from_le_bytes(self.take()?)),
+            IdlType::U128 => u128::from_le_bytes(self.take()?).to_string().into(),
+            IdlType::I128 => i128::from_le_bytes(self.take()?).to_string().into(),
+            IdlType::U256 | IdlType::I256 => {
+                // Big-endian hex; two's complement for i256.
+                let bytes = self.take::<32>()?;
+                let hex: String = bytes.iter().rev().map(|byte| format!("{:02x}", byte)).collect();
+                format!("0x{}", hex).into()
+            }
+            IdlType::Bytes => {
+                let len = self.take_len()?;
+                self.take_slice(len)?.to_vec().into()
+            }
+            IdlType::String => {
+                let len = self.take_len()?;
+                let bytes = self.take_slice(len)?;
+                std::str::from_utf8(bytes)
+                    .map_err(|err| IdlError::InvalidData(err.to_string()))?
+                    .into()
+            }
+            IdlType::Pubkey => bs58::encode(self.take::<32>()?).into_string().into(),
+            IdlType::Option(inner) => match self.take::<1>()?[0] {
+                0 => Value::Null,
+                1 => self.decode(idl, inner)?,
+                other => return Err(IdlError::InvalidData(format!("invalid option tag {}", other))),
+            },
+            IdlType::COption(inner) => {
+                let tag = u32::from_le_bytes(self.take()?);
+                // The value is present, zeroed, even when the tag is 0.
+                let value = self.decode(idl, inner)?;
+                match tag {
+                    0 => Value::Null,
+                    1 => value,
+                    other => return Err(IdlError::InvalidData(format!("invalid coption tag {}", other))),
+                }
+            }
+            IdlType::Vec(inner) => {
+                let len = self.take_len()?;
+                // Every element takes at least a byte in practice.
+                if len > self.data.len() {
+                    return Err(IdlError::UnexpectedEnd);
+                }
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(self.decode(idl, inner)?);
+                }
+                Value::Array(items)
+            }
+            IdlType::Array(inner, len) => {
+                // As for vectors, which also bounds arrays of types that
+                // decode from no bytes at all.
+                if *len > self.data.len() {
+                    return Err(IdlError::UnexpectedEnd);
+                }
+                let mut items = Vec::with_capacity(*len);
+                for _ in 0..*len {
+                    items.push(self.decode(idl, inner)?);
+                }
+                Value::Array(items)
+            }
+            IdlType::Defined(name) => {
+                let def = idl.type_def(name)?;
+                self.decode_type_def(idl, def)?
+            }
+            IdlType::Generic(name) => return Err(IdlError::Unsupported(format!("generic {}", name))),
+        })
+    }
+
+    pub(crate) fn decode_type_def(&mut self, idl: &Idl, def: &IdlTypeDefTy) -> Result<Value, IdlError> {
+        match def {
+            IdlTypeDefTy::Struct { fields: None } => Ok(Value::Object(Map::new())),
+            IdlTypeDefTy::Struct { fields: Some(fields) } => self.decode_fields(idl, fields),
+            IdlTypeDefTy::Enum { variants } => {
+                let index = self.take::<1>()?[0] as usize;
+                let variant = variants
+                    .get(index)
+                    .ok_or_else(|| IdlError::InvalidData(format!("invalid enum variant {}", index)))?;
+                match &variant.fields {
+                    None => Ok(Value::String(variant.name.clone())),
+                    Some(fields) => {
+                        let value = self.decode_fields(idl, fields)?;
+                        Ok(json!({ variant.name.clone(): value }))
+                    }
+                }
+            }
+            IdlTypeDefTy::Type { alias } => self.decode(idl, alias),
+        }
+    }
+
+    pub(crate) fn decode_fields(&mut self, idl: &Idl, fields: &IdlFields) -> Result<Value, IdlError> {
+        match fields {
+            IdlFields::Named(fields) => {
+                let mut object = Map::new();
+                for field in fields {
+                    object.insert(field.name.clone(), self.decode(idl, &field.ty)?);
+                }
+                Ok(Value::Object(object))
+            }
+            IdlFields::Tuple(types) => {
+                let mut items = Vec::with_capacity(types.len());
+                for ty in types {
+                    items.push(self.decode(idl, ty)?);
+                }
+                Ok(Value::Array(items))
+            }
+        }
+    }
+}
@@ -0,0 +1,641 @@
+//! On-chain Anchor IDLs.
+//!
+//! Anchor programs can publish their IDL to an account derived from the
+//! program id. The account holds the IDL as zlib-compressed JSON, which is
+//! enough to decode the program's accounts and instructions without Rust
+//! types for them.
+//!
+//! Both the current IDL format (Anchor 0.30 and later, with explicit
+//! discriminators) and the legacy format are understood.
+
+use super::sha256::sha256;
+use super::{account_discriminator, DISCRIMINATOR_LEN};
+use crate::client::JsonRpcClient;
+use crate::deserialize::{strip_discriminator, AccountDeserializeError};
+use crate::error::Result;
+use crate::methods::account::{AccountInfoConfig, RpcGetAccountInfoRequest};
+use crate::types::Encoding;
+use serde::{Deserialize, Deserializer};
+use std::io::Read;
+use thiserror::Error;
+
+const IDL_SEED: &str = "anchor:idl";
+// Far above any published IDL; bounds the output of a hostile zlib stream.
+const MAX_IDL_LEN: u64 = 16 * 1024 * 1024;
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+#[derive(Error, Debug)]
+pub enum IdlError {
+    #[error("Invalid public key: {0}")]
+    InvalidPubkey(String),
+
+    #[error("Invalid IDL account: {0}")]
+    InvalidAccount(String),
+
+    #[error(transparent)]
+    Account(#[from] AccountDeserializeError),
+
+    #[error("Failed to decompress IDL: {0}")]
+    Decompress(#[from] std::io::Error),
+
+    #[error("Decompressed IDL exceeds {0} bytes")]
+    TooLarge(u64),
+
+    #[error("Invalid IDL: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("No account in the IDL matches discriminator {0:?}")]
+    UnknownAccount(Vec<u8>),
+
+    #[error("No instruction in the IDL matches discriminator {0:?}")]
+    UnknownInstruction(Vec<u8>),
+
+    #[error("Type {0} is not defined in the IDL")]
+    UnknownType(String),
+
+    #[error("Unsupported IDL type: {0}")]
+    Unsupported(String),
+
+    #[error("Data ended before the value was fully decoded")]
+    UnexpectedEnd,
+
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+}
+
+/// An Anchor IDL.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Idl {
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub metadata: Option<IdlMetadata>,
+    /// Program name, in legacy IDLs. See [`Idl::name`].
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Program version, in legacy IDLs.
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub instructions: Vec<IdlInstruction>,
+    #[serde(default)]
+    pub accounts: Vec<IdlAccount>,
+    #[serde(default)]
+    pub types: Vec<IdlTypeDef>,
+    #[serde(default)]
+    pub errors: Vec<IdlErrorCode>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlMetadata {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub spec: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlInstruction {
+    pub name: String,
+    #[serde(default)]
+    pub discriminator: Option<Vec<u8>>,
+    /// The instruction's accounts, kept as written in the IDL.
+    #[serde(default)]
+    pub accounts: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub args: Vec<IdlField>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlAccount {
+    pub name: String,
+    #[serde(default)]
+    pub discriminator: Option<Vec<u8>>,
+    /// The account layout, in legacy IDLs. Current IDLs define it in
+    /// [`Idl::types`] under the same name.
+    #[serde(default, rename = "type")]
+    pub ty: Option<IdlTypeDefTy>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlTypeDef {
+    pub name: String,
+    /// `borsh` unless the type is zero-copy.
+    #[serde(default)]
+    pub serialization: Option<String>,
+    #[serde(rename = "type")]
+    pub ty: IdlTypeDefTy,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum IdlTypeDefTy {
+    Struct {
+        #[serde(default)]
+        fields: Option<IdlFields>,
+    },
+    Enum {
+        variants: Vec<IdlEnumVariant>,
+    },
+    Type {
+        alias: IdlType,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum IdlFields {
+    Named(Vec<IdlField>),
+    Tuple(Vec<IdlType>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: IdlType,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlEnumVariant {
+    pub name: String,
+    #[serde(default)]
+    pub fields: Option<IdlFields>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IdlErrorCode {
+    pub code: u32,
+    pub name: String,
+    #[serde(default)]
+    pub msg: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdlType {
+    Bool,
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    F32,
+    U64,
+    I64,
+    F64,
+    U128,
+    I128,
+    U256,
+    I256,
+    Bytes,
+    String,
+    Pubkey,
+    Option(Box<IdlType>),
+    /// A fixed-size option with a 4-byte tag, as used by SPL Token.
+    COption(Box<IdlType>),
+    Vec(Box<IdlType>),
+    Array(Box<IdlType>, usize),
+    Defined(String),
+    Generic(String),
+}
+
+impl<'de> Deserialize<'de> for IdlType {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Primitive(String),
+            Composite(Composite),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Composite {
+            Option(Box<IdlType>),
+            COption(Box<IdlType>),
+            Vec(Box<IdlType>),
+            Array((Box<IdlType>, ArrayLen)),
+            Defined(Defined),
+            Generic(String),
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum ArrayLen {
+            Value(usize),
+            Generic { generic: String },
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Defined {
+            Legacy(String),
+            Named { name: String },
+        }
+
+        use serde::de::Error;
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Primitive(name) => match name.as_str() {
+                "bool" => IdlType::Bool,
+                "u8" => IdlType::U8,
+                "i8" => IdlType::I8,
+                "u16" => IdlType::U16,
+                "i16" => IdlType::I16,
+                "u32" => IdlType::U32,
+                "i32" => IdlType::I32,
+                "f32" => IdlType::F32,
+                "u64" => IdlType::U64,
+                "i64" => IdlType::I64,
+                "f64" => IdlType::F64,
+                "u128" => IdlType::U128,
+                "i128" => IdlType::I128,
+                "u256" => IdlType::U256,
+                "i256" => IdlType::I256,
+                "bytes" => IdlType::Bytes,
+                "string" => IdlType::String,
+                "pubkey" | "publicKey" => IdlType::Pubkey,
+                other => return Err(D::Error::custom(format!("unknown IDL type {}", other))),
+            },
+            Repr::Composite(Composite::Option(ty)) => IdlType::Option(ty),
+            Repr::Composite(Composite::COption(ty)) => IdlType::COption(ty),
+            Repr::Composite(Composite::Vec(ty)) => IdlType::Vec(ty),
+            Repr::Composite(Composite::Array((ty, ArrayLen::Value(len)))) => IdlType::Array(ty, len),
+            Repr::Composite(Composite::Array((_, ArrayLen::Generic { generic }))) => IdlType::Generic(generic),
+            Repr::Composite(Composite::Defined(Defined::Legacy(name) | Defined::Named { name })) => {
+                IdlType::Defined(name)
+            }
+            Repr::Composite(Composite::Generic(name)) => IdlType::Generic(name),
+        })
+    }
+}
+
+/// An account decoded with an [`Idl`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedAccount {
+    pub name: String,
+    pub data: serde_json::Value,
+}
+
+/// Instruction data decoded with an [`Idl`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedInstruction {
+    pub name: String,
+    pub args: serde_json::Value,
+}
+
+impl Idl {
+    /// Parses the data of an IDL account: discriminator, authority, then
+    /// the length-prefixed, zlib-compressed IDL JSON.
+    pub fn from_account_data(data: &[u8]) -> std::result::Result<Self, IdlError> {
+        let data = strip_discriminator(data, &account_discriminator("IdlAccount"))?;
+        if data.len() < 36 {
+            return Err(IdlError::InvalidAccount("account is too small".to_string()));
+        }
+
+        let len = u32::from_le_bytes(data[32..36].try_into().unwrap()) as usize;
+        let compressed = data
+            .get(36..36 + len)
+            .ok_or_else(|| IdlError::InvalidAccount(format!("IDL length {} exceeds account data", len)))?;
+
+        let mut json = Vec::new();
+        flate2::read::ZlibDecoder::new(compressed)
+            .take(MAX_IDL_LEN + 1)
+            .read_to_end(&mut json)?;
+        if json.len() as u64 > MAX_IDL_LEN {
+            return Err(IdlError::TooLarge(MAX_IDL_LEN));
+        }
+        Ok(serde_json::from_slice(&json)?)
+    }
+
+    /// The program name, from either IDL format.
+    pub fn name(&self) -> Option<&str> {
+        self.metadata
+            .as_ref()
+            .map(|metadata| metadata.name.as_str())
+            .or(self.name.as_deref())
+    }
+
+    /// Finds the account whose discriminator starts `data` and decodes the
+    /// rest according to its layout.
+    pub fn decode_account(&self, data: &[u8]) -> std::result::Result<DecodedAccount, IdlError> {
+        let account = self
+            .accounts
+            .iter()
+            .find(|account| data.starts_with(&account_discriminator_of(account)))
+            .ok_or_else(|| IdlError::UnknownAccount(data[..data.len().min(DISCRIMINATOR_LEN)].to_vec()))?;
+
+        let discriminator_len = account_discriminator_of(account).len();
+        let mut reader = super::dynamic::Reader::new(&data[discriminator_len..]);
+        let data = match &account.ty {
+            Some(ty) => reader.decode_type_def(self, ty)?,
+            None => reader.decode(self, &IdlType::Defined(account.name.clone()))?,
+        };
+
+        Ok(DecodedAccount {
+            name: account.name.clone(),
+            data,
+        })
+    }
+
+    /// Finds the instruction whose discriminator starts `data` and decodes
+    /// its arguments into an object keyed by argument name.
+    pub fn decode_instruction(&self, data: &[u8]) -> std::result::Result<DecodedInstruction, IdlError> {
+        let instruction = self
+            .instructions
+            .iter()
+            .find(|instruction| data.starts_with(&instruction_discriminator_of(instruction)))
+            .ok_or_else(|| IdlError::UnknownInstruction(data[..data.len().min(DISCRIMINATOR_LEN)].to_vec()))?;
+
+        let discriminator_len = instruction_discriminator_of(instruction).len();
+        let mut reader = super::dynamic::Reader::new(&data[discriminator_len..]);
+        let args = reader.decode_fields(self, &IdlFields::Named(instruction.args.clone()))?;
+
+        Ok(DecodedInstruction {
+            name: instruction.name.clone(),
+            args,
+        })
+    }
+
+    pub(crate) fn type_def(&self, name: &str) -> std::result::Result<&IdlTypeDefTy, IdlError> {
+        if let Some(def) = self.types.iter().find(|def| def.name == name) {
+            match def.serialization.as_deref() {
+                None | Some("borsh") => return Ok(&def.ty),
+                Some(other) => return Err(IdlError::Unsupported(format!("{} serialization of {}", other, name))),
+            }
+        }
+        // Legacy IDLs may refer to account layouts as types.
+        self.accounts
+            .iter()
+            .find(|account| account.name == name)
+            .and_then(|account| account.ty.as_ref())
+            .ok_or_else(|| IdlError::UnknownType(name.to_string()))
+    }
+}
+
+fn account_discriminator_of(account: &IdlAccount) -> Vec<u8> {
+    account
+        .discriminator
+        .clone()
+        .unwrap_or_else(|| account_discriminator(&account.name).to_vec())
+}
+
+fn instruction_discriminator_of(instruction: &IdlInstruction) -> Vec<u8> {
+    instruction
+        .discriminator
+        .clone()
+        .unwrap_or_else(|| super::instruction_discriminator(&snake_case(&instruction.name)).to_vec())
+}
+
+// Legacy IDLs name instructions in camel case, while the discriminator is
+// derived from the snake case name in the program. Follows the word
+// boundaries of `heck::ToSnakeCase`, which Anchor uses, so that runs of
+// capitals stay one word: `initNFT` is `init_nft`.
+fn snake_case(name: &str) -> String {
+    let mut words = Vec::new();
+    for part in name.split(|c: char| !c.is_alphanumeric()) {
+        let mut chars = part.char_indices().peekable();
+        let mut start = 0;
+        // Whether the word so far ends in a lowercase or uppercase run;
+        // digits continue either.
+        let mut upper = None;
+        while let Some((i, c)) = chars.next() {
+            let Some(&(next_i, next)) = chars.peek() else {
+                words.push(&part[start..]);
+                break;
+            };
+            let current = if c.is_lowercase() {
+                Some(false)
+            } else if c.is_uppercase() {
+                Some(true)
+            } else {
+                upper
+            };
+            if current == Some(false) && next.is_uppercase() {
+                // `initNft`: a boundary after the lowercase letter.
+                words.push(&part[start..next_i]);
+                start = next_i;
+                upper = None;
+            } else if upper == Some(true) && c.is_uppercase() && next.is_lowercase() {
+                // `HTTPServer`: the last capital starts the next word.
+                words.push(&part[start..i]);
+                start = i;
+                upper = None;
+            } else {
+                upper = current;
+            }
+        }
+    }
+    words.join("_").to_lowercase()
+}
+
+/// The address of the IDL account of `program_id`.
+pub fn idl_address(program_id: &str) -> std::result::Result<String, IdlError> {
+    let program = decode_pubkey(program_id)?;
+    let base = find_program_address(&[], &program);
+
+    let mut preimage = Vec::with_capacity(32 + IDL_SEED.len() + 32);
+    preimage.extend_from_slice(&base);
+    preimage.extend_from_slice(IDL_SEED.as_bytes());
+    preimage.extend_from_slice(&program);
+    Ok(bs58::encode(sha256(&preimage, &[])).into_string())
+}
+
+fn decode_pubkey(pubkey: &str) -> std::result::Result<[u8; 32], IdlError> {
+    bs58::decode(pubkey)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| IdlError::InvalidPubkey(pubkey.to_string()))
+}
+
+// The first bump, counting down from 255, for which the hash is not a valid
+// ed25519 point. A valid address exists with overwhelming probability.
+fn find_program_address(seeds: &[&[u8]], program: &[u8; 32]) -> [u8; 32] {
+    for bump in (0..=u8::MAX).rev() {
+        let mut preimage = Vec::new();
+        for seed in seeds {
+            preimage.extend_from_slice(seed);
+        }
+        preimage.push(bump);
+        preimage.extend_from_slice(program);
+        preimage.extend_from_slice(PDA_MARKER);
+
+        let hash = sha256(&preimage, &[]);
+        if curve25519_dalek::edwards::CompressedEdwardsY(hash).decompress().is_none() {
+            return hash;
+        }
+    }
+    unreachable!("no program address found")
+}
+
+impl JsonRpcClient {
+    /// Fetches and parses the IDL published by an Anchor program. Returns
+    /// `None` when the program has no IDL account.
+    pub async fn get_idl(&self, program_id: &str) -> Result<Option<Idl>> {
+        let address = idl_address(program_id)?;
+        let response = self
+            .call(RpcGetAccountInfoRequest {
+                pubkey: address,
+                config: Some(AccountInfoConfig {
+                    encoding: Some(Encoding::Base64),
+                    ..Default::default()
+                }),
+            })
+            .await?;
+
+        let Some(info) = response.into_value() else {
+            return Ok(None);
+        };
+        let data = info.data_bytes().map_err(AccountDeserializeError::from).map_err(IdlError::from)?;
+        Ok(Some(Idl::from_account_data(&data)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::ZlibEncoder;
+    use serde_json::json;
+    use std::io::Write;
+
+    fn account_data(json: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(json).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut data = account_discriminator("IdlAccount").to_vec();
+        data.extend([0; 32]);
+        data.extend((compressed.len() as u32).to_le_bytes());
+        data.extend(compressed);
+        data
+    }
+
+    fn legacy_idl() -> Idl {
+        serde_json::from_value(json!({
+            "version": "0.1.0",
+            "name": "nft",
+            "instructions": [{
+                "name": "initNFT",
+                "accounts": [],
+                "args": [{ "name": "supply", "type": "u64" }, { "name": "label", "type": { "option": "string" } }],
+            }],
+            "accounts": [{
+                "name": "Collection",
+                "type": {
+                    "kind": "struct",
+                    "fields": [
+                        { "name": "authority", "type": "publicKey" },
+                        { "name": "kind", "type": { "defined": "Kind" } },
+                        { "name": "markers", "type": { "array": [{ "defined": "Marker" }, 3] } },
+                    ],
+                },
+            }],
+            "types": [
+                { "name": "Kind", "type": { "kind": "enum", "variants": [{ "name": "Open" }, { "name": "Capped", "fields": ["u32"] }] } },
+                { "name": "Marker", "type": { "kind": "struct" } },
+            ],
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn snake_case_matches_heck() {
+        let cases = [
+            ("initialize", "initialize"),
+            ("initNft", "init_nft"),
+            ("initNFT", "init_nft"),
+            ("HTTPServer", "http_server"),
+            ("createHTTPServerV2", "create_http_server_v2"),
+            ("setV2Config", "set_v2_config"),
+            ("already_snake", "already_snake"),
+        ];
+        for (name, expected) in cases {
+            assert_eq!(snake_case(name), expected, "{}", name);
+        }
+    }
+
+    #[test]
+    fn derives_the_idl_address() {
+        assert_eq!(
+            idl_address("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS").unwrap(),
+            "9j6oH2BscegWPVpeiP2mbsN35pDbiBSpyZrpi86S8eWF"
+        );
+        assert!(matches!(idl_address("not a pubkey"), Err(IdlError::InvalidPubkey(_))));
+    }
+
+    #[test]
+    fn reads_the_idl_account() {
+        let idl = Idl::from_account_data(&account_data(br#"{"metadata":{"name":"vault","version":"0.1.0"}}"#)).unwrap();
+        assert_eq!(idl.name(), Some("vault"));
+
+        let mut truncated = account_data(b"{}");
+        truncated.truncate(truncated.len() - 1);
+        assert!(matches!(Idl::from_account_data(&truncated), Err(IdlError::InvalidAccount(_))));
+    }
+
+    #[test]
+    fn bounds_the_decompressed_idl() {
+        let json = vec![b' '; MAX_IDL_LEN as usize + 1];
+        assert!(matches!(
+            Idl::from_account_data(&account_data(&json)),
+            Err(IdlError::TooLarge(MAX_IDL_LEN))
+        ));
+    }
+
+    #[test]
+    fn decodes_legacy_instructions_by_snake_case_name() {
+        let idl = legacy_idl();
+        let mut data = super::super::instruction_discriminator("init_nft").to_vec();
+        data.extend(10u64.to_le_bytes());
+        data.extend([1, 2, 0, 0, 0, b'o', b'k']);
+
+        let decoded = idl.decode_instruction(&data).unwrap();
+        assert_eq!(decoded.name, "initNFT");
+        assert_eq!(decoded.args, json!({ "supply": 10, "label": "ok" }));
+    }
+
+    #[test]
+    fn decodes_accounts() {
+        let idl = legacy_idl();
+        let mut data = account_discriminator("Collection").to_vec();
+        data.extend([0; 32]);
+        data.extend([1, 5, 0, 0, 0]);
+        // Accounts are usually allocated with room to spare.
+        data.extend([0; 8]);
+
+        let decoded = idl.decode_account(&data).unwrap();
+        assert_eq!(decoded.name, "Collection");
+        assert_eq!(
+            decoded.data,
+            json!({
+                "authority": "11111111111111111111111111111111",
+                "kind": { "Capped": [5] },
+                "markers": [{}, {}, {}],
+            })
+        );
+
+        assert!(matches!(
+            idl.decode_account(&[0; 8]),
+            Err(IdlError::UnknownAccount(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_arrays_longer_than_the_data() {
+        let idl: Idl = serde_json::from_value(json!({
+            "accounts": [{
+                "name": "Huge",
+                "type": { "kind": "struct", "fields": [{ "name": "items", "type": { "array": [{ "defined": "Empty" }, 4294967295u64] } }] },
+            }],
+            "types": [{ "name": "Empty", "type": { "kind": "struct" } }],
+        }))
+        .unwrap();
+
+        let data = account_discriminator("Huge");
+        assert!(matches!(idl.decode_account(&data), Err(IdlError::UnexpectedEnd)));
+    }
+}
@@ -0,0 +1,144 @@
+//! Helpers for programs built with Anchor.
+//!
+//! Anchor prefixes every account with an 8-byte discriminator derived from
+//! the account's type name, and every instruction with one derived from the
+//! instruction's name.
+
+#[cfg(feature = "idl")]
+mod dynamic;
+#[cfg(feature = "idl")]
+pub mod idl;
+mod sha256;
+
+use crate::methods::program::{ProgramAccountFilter, ProgramAccountsConfig, RpcGetProgramAccountsRequest};
+use crate::types::Encoding;
+
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// `sha256("account:<name>")[..8]`, the discriminator of the account type
+/// `name`. Usable in constants:
+///
+/// ```
+/// use solana_jsonrpc_client::anchor::account_discriminator;
+///
+/// const VAULT: [u8; 8] = account_discriminator("Vault");
+/// ```
+pub const fn account_discriminator(name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    discriminator(b"account:", name)
+}
+
+/// `sha256("global:<name>")[..8]`, the discriminator of the instruction
+/// `name`, in snake case as it appears in the program.
+pub const fn instruction_discriminator(name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    discriminator(b"global:", name)
+}
+
+const fn discriminator(namespace: &[u8], name: &str) -> [u8; DISCRIMINATOR_LEN] {
+    let hash = sha256::sha256(namespace, name.as_bytes());
+    let mut discriminator = [0u8; DISCRIMINATOR_LEN];
+    let mut i = 0;
+    while i < DISCRIMINATOR_LEN {
+        discriminator[i] = hash[i];
+        i += 1;
+    }
+    discriminator
+}
+
+/// Matches accounts of the Anchor account type `name`.
+pub fn account_filter(name: &str) -> ProgramAccountFilter {
+    ProgramAccountFilter::discriminator(&account_discriminator(name))
+}
+
+/// A `getProgramAccounts` request for every `name` account of `program_id`,
+/// with base64 data.
+pub fn program_accounts_request(program_id: impl Into<String>, name: &str) -> RpcGetProgramAccountsRequest {
+    RpcGetProgramAccountsRequest {
+        program_id: program_id.into(),
+        config: Some(ProgramAccountsConfig {
+            encoding: Some(Encoding::Base64),
+            filters: Some(vec![account_filter(name)]),
+            ..Default::default()
+        }),
+    }
+}
+
+/// An Anchor account type, decoded with Borsh after its discriminator.
+///
+/// Implementing this is enough to use the type with
+/// [`JsonRpcClient::get_account_as`](crate::JsonRpcClient::get_account_as)
+/// and
+/// [`JsonRpcClient::get_program_accounts_as`](crate::JsonRpcClient::get_program_accounts_as):
+///
+/// ```ignore
+/// impl AnchorAccount for Vault {
+///     const NAME: &'static str = "Vault";
+/// }
+///
+/// let vaults: Vec<(String, Vault)> = client.get_program_accounts_as(program_id, vec![]).await?;
+/// ```
+#[cfg(feature = "borsh")]
+pub trait AnchorAccount: borsh::BorshDeserialize {
+    /// The account's Rust type name in the program.
+    const NAME: &'static str;
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN] = account_discriminator(Self::NAME);
+}
+
+#[cfg(feature = "borsh")]
+impl<T: AnchorAccount> crate::deserialize::AccountDeserialize for T {
+    const DISCRIMINATOR: &'static [u8] = &<T as AnchorAccount>::DISCRIMINATOR;
+
+    fn deserialize_account(data: &[u8]) -> Result<Self, crate::deserialize::AccountDeserializeError> {
+        crate::deserialize::deserialize_borsh(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discriminators_match_anchor() {
+        assert_eq!(instruction_discriminator("initialize"), [175, 175, 109, 31, 13, 152, 155, 237]);
+        assert_eq!(account_discriminator("Vault"), [211, 8, 232, 43, 2, 152, 117, 119]);
+    }
+
+    #[test]
+    fn program_accounts_request_filters_by_account_type() {
+        let request = program_accounts_request("program", "Vault");
+        let config = request.config.unwrap();
+        assert_eq!(config.encoding, Some(Encoding::Base64));
+        assert_eq!(config.filters.unwrap(), vec![account_filter("Vault")]);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn anchor_accounts_check_their_discriminator() {
+        use crate::deserialize::{decode_account, AccountDeserializeError};
+
+        #[derive(Debug, PartialEq)]
+        struct Vault {
+            amount: u64,
+        }
+
+        impl borsh::BorshDeserialize for Vault {
+            fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+                Ok(Vault {
+                    amount: u64::deserialize_reader(reader)?,
+                })
+            }
+        }
+
+        impl AnchorAccount for Vault {
+            const NAME: &'static str = "Vault";
+        }
+
+        let data = [&account_discriminator("Vault")[..], &9u64.to_le_bytes()].concat();
+        assert_eq!(decode_account::<Vault>(&data).unwrap(), Vault { amount: 9 });
+
+        let other = [&account_discriminator("Pool")[..], &9u64.to_le_bytes()].concat();
+        assert!(matches!(
+            decode_account::<Vault>(&other),
+            Err(AccountDeserializeError::Discriminator { .. })
+        ));
+    }
+}
@@ -0,0 +1,142 @@
+// SHA-256, as a `const fn` so discriminators can be computed at compile time.
+// Hashes the concatenation of two slices to avoid building the preimage.
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+const H: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+pub(crate) const fn sha256(a: &[u8], b: &[u8]) -> [u8; 32] {
+    let len = a.len() + b.len();
+    // Message, 0x80, zero padding, then the 64-bit bit length.
+    let padded = (len + 9).div_ceil(64) * 64;
+
+    let mut state = H;
+    let mut block = 0;
+    while block < padded / 64 {
+        let mut w = [0u32; 64];
+        let mut i = 0;
+        while i < 16 {
+            let mut word = 0u32;
+            let mut j = 0;
+            while j < 4 {
+                let byte = message_byte(a, b, len, padded, block * 64 + i * 4 + j);
+                word = (word << 8) | byte as u32;
+                j += 1;
+            }
+            w[i] = word;
+            i += 1;
+        }
+        while i < 64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+            i += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        i = 0;
+        while i < 64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+            i += 1;
+        }
+
+        let out = [a, b, c, d, e, f, g, h];
+        i = 0;
+        while i < 8 {
+            state[i] = state[i].wrapping_add(out[i]);
+            i += 1;
+        }
+        block += 1;
+    }
+
+    let mut digest = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        digest[i] = (state[i / 4] >> (24 - 8 * (i % 4))) as u8;
+        i += 1;
+    }
+    digest
+}
+
+const fn message_byte(a: &[u8], b: &[u8], len: usize, padded: usize, index: usize) -> u8 {
+    if index < a.len() {
+        a[index]
+    } else if index < len {
+        b[index - a.len()]
+    } else if index == len {
+        0x80
+    } else if index >= padded - 8 {
+        let bits = (len as u64) * 8;
+        (bits >> (8 * (padded - 1 - index))) as u8
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(hash: [u8; 32]) -> String {
+        hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn matches_the_fips_vectors() {
+        assert_eq!(
+            hex(sha256(b"", b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(sha256(b"abc", b"")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex(sha256(b"abcdbcdecdefdefgefghfghighijhijkijkl", b"jklmklmnlmnomnopnopq")),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn pads_across_block_boundaries() {
+        // 55 bytes leave room for the length in one block, 56 do not, and 64
+        // fill a block exactly. 200 bytes span four blocks.
+        let cases = [
+            (55, "9f4390f8d30c2dd92ec9f095b65e2b9ae9b0a925a5258e241c9f1e910f734318"),
+            (56, "b35439a4ac6f0948b6d6f9e3c6af0f5f590ce20f1bde7090ef7970686ec6738a"),
+            (64, "ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb"),
+            (200, "c2a908d98f5df987ade41b5fce213067efbcc21ef2240212a41e54b5e7c28ae5"),
+        ];
+        for (len, expected) in cases {
+            let message = vec![b'a'; len];
+            assert_eq!(hex(sha256(&message, b"")), expected, "{} bytes", len);
+            assert_eq!(hex(sha256(b"", &message)), expected, "{} bytes", len);
+            let (a, b) = message.split_at(len / 3);
+            assert_eq!(hex(sha256(a, b)), expected, "{} bytes split", len);
+        }
+    }
+}
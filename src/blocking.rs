@@ -0,0 +1,52 @@
+use crate::client::{CallOptions, RpcRequest};
+use crate::error::Result;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// Synchronous wrapper around [`crate::JsonRpcClient`] for code without an
+/// async runtime.
+///
+/// Calls are driven on an internal single-threaded runtime. They must not be
+/// made from within another tokio runtime, where blocking would panic.
+#[derive(Debug, Clone)]
+pub struct JsonRpcClient {
+    inner: crate::JsonRpcClient,
+    runtime: Arc<Runtime>,
+}
+
+impl JsonRpcClient {
+    pub fn connect(url: impl Into<String>) -> Self {
+        Self::from_async(crate::JsonRpcClient::connect(url))
+    }
+
+    /// Wraps an already configured async client.
+    pub fn from_async(inner: crate::JsonRpcClient) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to build tokio runtime for blocking client");
+
+        Self {
+            inner,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    pub fn call<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: RpcRequest,
+    {
+        self.runtime.block_on(self.inner.call(request))
+    }
+
+    pub fn call_with_options<R>(&self, request: R, options: CallOptions) -> Result<R::Response>
+    where
+        R: RpcRequest,
+    {
+        self.runtime.block_on(self.inner.call_with_options(request, options))
+    }
+
+    pub fn inner(&self) -> &crate::JsonRpcClient {
+        &self.inner
+    }
+}
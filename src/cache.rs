@@ -0,0 +1,307 @@
+use lru::LruCache;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a response for a given method may be served from the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Never cache responses for this method.
+    Never,
+    /// Cache responses for a fixed duration.
+    Ttl(Duration),
+    /// Cache responses forever. Only applied when the request is made at
+    /// `finalized` commitment and the result is not `null`, otherwise the
+    /// response is not cached at all.
+    Permanent,
+}
+
+/// Backend used by [`ResponseCache`] to store serialized results.
+///
+/// Keys are the method name followed by the serialized params without
+/// `minContextSlot`, values are the raw `result` field of the JSON-RPC
+/// response.
+pub trait CacheStorage: Send + Sync {
+    fn get(&self, key: &str) -> Option<serde_json::Value>;
+    fn insert(&self, key: String, value: serde_json::Value, ttl: Option<Duration>);
+    fn remove(&self, key: &str);
+    fn clear(&self);
+}
+
+/// In-memory least-recently-used storage.
+pub struct LruStorage {
+    entries: Mutex<LruCache<String, (serde_json::Value, Option<Instant>)>>,
+}
+
+impl LruStorage {
+    pub fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+}
+
+impl CacheStorage for LruStorage {
+    fn get(&self, key: &str) -> Option<serde_json::Value> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((_, Some(expires_at))) if *expires_at <= Instant::now() => {
+                entries.pop(key);
+                None
+            }
+            Some((value, _)) => Some(value.clone()),
+            None => None,
+        }
+    }
+
+    fn insert(&self, key: String, value: serde_json::Value, ttl: Option<Duration>) {
+        let expires_at = ttl.map(|ttl| Instant::now() + ttl);
+        self.entries.lock().unwrap().put(key, (value, expires_at));
+    }
+
+    fn remove(&self, key: &str) {
+        self.entries.lock().unwrap().pop(key);
+    }
+
+    fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+/// Method-aware response cache used by [`JsonRpcClient`](crate::JsonRpcClient).
+///
+/// `minContextSlot` is left out of the key, so requests from a
+/// [`Session`](crate::Session) share entries with plain requests. A cached
+/// result whose context slot is older than the request's `minContextSlot`
+/// is a miss; results without a context slot are served as they are.
+pub struct ResponseCache {
+    storage: Box<dyn CacheStorage>,
+    policies: HashMap<String, CachePolicy>,
+}
+
+impl ResponseCache {
+    /// Creates a cache with no policies; every method is uncached until a
+    /// policy is added with [`ResponseCache::with_policy`].
+    pub fn new(storage: impl CacheStorage + 'static) -> Self {
+        Self {
+            storage: Box::new(storage),
+            policies: HashMap::new(),
+        }
+    }
+
+    /// Creates an in-memory LRU cache with the default policies.
+    pub fn lru(capacity: NonZeroUsize) -> Self {
+        Self::new(LruStorage::new(capacity)).with_default_policies()
+    }
+
+    /// Registers the default policies for methods with immutable or
+    /// short-lived responses.
+    pub fn with_default_policies(self) -> Self {
+        self.with_policy("getGenesisHash", CachePolicy::Permanent)
+            .with_policy("getEpochSchedule", CachePolicy::Permanent)
+            .with_policy("getBlock", CachePolicy::Permanent)
+            .with_policy("getTransaction", CachePolicy::Permanent)
+            .with_policy("getLatestBlockhash", CachePolicy::Ttl(Duration::from_secs(2)))
+            .with_policy(
+                "getMinimumBalanceForRentExemption",
+                CachePolicy::Ttl(Duration::from_secs(60)),
+            )
+    }
+
+    pub fn with_policy(mut self, method: impl Into<String>, policy: CachePolicy) -> Self {
+        self.policies.insert(method.into(), policy);
+        self
+    }
+
+    pub fn policy(&self, method: &str) -> CachePolicy {
+        self.policies.get(method).copied().unwrap_or(CachePolicy::Never)
+    }
+
+    pub fn clear(&self) {
+        self.storage.clear();
+    }
+
+    /// Returns the cache key and policy for a request, or `None` if the
+    /// request must not be served from or stored in the cache.
+    pub(crate) fn key(&self, method: &str, params: &serde_json::Value) -> Option<(String, CachePolicy)> {
+        let policy = match self.policy(method) {
+            CachePolicy::Never => return None,
+            CachePolicy::Permanent if !is_finalized(params) => return None,
+            policy => policy,
+        };
+        Some((format!("{}:{}", method, without_min_context_slot(params)), policy))
+    }
+
+    pub(crate) fn get(&self, key: &str, min_context_slot: Option<u64>) -> Option<serde_json::Value> {
+        let value = self.storage.get(key)?;
+        match (min_context_slot, context_slot(&value)) {
+            (Some(min_context_slot), Some(slot)) if slot < min_context_slot => None,
+            _ => Some(value),
+        }
+    }
+
+    pub(crate) fn insert(&self, key: String, policy: CachePolicy, value: &serde_json::Value) {
+        match policy {
+            CachePolicy::Never => {}
+            CachePolicy::Ttl(ttl) => self.storage.insert(key, value.clone(), Some(ttl)),
+            CachePolicy::Permanent => {
+                if !value.is_null() {
+                    self.storage.insert(key, value.clone(), None);
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("policies", &self.policies)
+            .finish_non_exhaustive()
+    }
+}
+
+// The server defaults to `finalized` when no commitment is given, so a request
+// only counts as unfinalized if one of its params explicitly says otherwise.
+fn is_finalized(params: &serde_json::Value) -> bool {
+    let commitment_of = |param: &serde_json::Value| match param {
+        serde_json::Value::Object(config) => config
+            .get("commitment")
+            .and_then(|c| c.as_str())
+            .map(str::to_owned),
+        serde_json::Value::String(s) if matches!(s.as_str(), "processed" | "confirmed" | "finalized") => {
+            Some(s.clone())
+        }
+        _ => None,
+    };
+
+    match params {
+        serde_json::Value::Array(params) => params
+            .iter()
+            .filter_map(commitment_of)
+            .all(|commitment| commitment == "finalized"),
+        _ => true,
+    }
+}
+
+pub(crate) fn min_context_slot(params: &serde_json::Value) -> Option<u64> {
+    params
+        .as_array()?
+        .iter()
+        .find_map(|param| param.get("minContextSlot")?.as_u64())
+}
+
+// Drops `minContextSlot` from every config, and configs left empty by that
+// at the end of the params, so `[pubkey, {minContextSlot}]` keys like
+// `[pubkey]`.
+fn without_min_context_slot(params: &serde_json::Value) -> serde_json::Value {
+    let serde_json::Value::Array(params) = params else {
+        return params.clone();
+    };
+    let mut params = params.clone();
+    for param in &mut params {
+        if let serde_json::Value::Object(config) = param {
+            config.remove("minContextSlot");
+        }
+    }
+    while params.last().is_some_and(|param| param.as_object().is_some_and(|config| config.is_empty())) {
+        params.pop();
+    }
+    serde_json::Value::Array(params)
+}
+
+fn context_slot(result: &serde_json::Value) -> Option<u64> {
+    result.get("context")?.get("slot")?.as_u64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cache() -> ResponseCache {
+        ResponseCache::lru(NonZeroUsize::new(2).unwrap())
+    }
+
+    #[test]
+    fn uncached_methods_have_no_key() {
+        assert_eq!(cache().key("getBalance", &json!(["abc"])), None);
+    }
+
+    #[test]
+    fn permanent_requires_finalized_commitment() {
+        let cache = cache();
+        assert!(cache.key("getBlock", &json!([1])).is_some());
+        assert!(cache.key("getBlock", &json!([1, { "commitment": "finalized" }])).is_some());
+        assert!(cache.key("getBlock", &json!([1, { "commitment": "confirmed" }])).is_none());
+        assert!(cache.key("getGenesisHash", &json!(["processed"])).is_none());
+    }
+
+    #[test]
+    fn ttl_ignores_commitment() {
+        let (_, policy) = cache()
+            .key("getLatestBlockhash", &json!([{ "commitment": "processed" }]))
+            .unwrap();
+        assert_eq!(policy, CachePolicy::Ttl(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn permanent_does_not_store_null() {
+        let cache = cache();
+        let (key, policy) = cache.key("getTransaction", &json!(["sig"])).unwrap();
+        cache.insert(key.clone(), policy, &serde_json::Value::Null);
+        assert_eq!(cache.get(&key, None), None);
+
+        cache.insert(key.clone(), policy, &json!({ "slot": 1 }));
+        assert_eq!(cache.get(&key, None), Some(json!({ "slot": 1 })));
+    }
+
+    #[test]
+    fn key_ignores_min_context_slot() {
+        let cache = cache().with_policy("getBalance", CachePolicy::Ttl(Duration::from_secs(1)));
+        let (plain, _) = cache.key("getBalance", &json!(["abc"])).unwrap();
+        let (session, _) = cache
+            .key("getBalance", &json!(["abc", { "minContextSlot": 5 }]))
+            .unwrap();
+        let (committed, _) = cache
+            .key("getBalance", &json!(["abc", { "commitment": "confirmed", "minContextSlot": 5 }]))
+            .unwrap();
+
+        assert_eq!(plain, session);
+        assert_eq!(committed, r#"getBalance:["abc",{"commitment":"confirmed"}]"#);
+        assert_eq!(min_context_slot(&json!(["abc", { "minContextSlot": 5 }])), Some(5));
+        assert_eq!(min_context_slot(&json!(["abc"])), None);
+    }
+
+    #[test]
+    fn results_older_than_min_context_slot_are_misses() {
+        let cache = cache().with_policy("getBalance", CachePolicy::Ttl(Duration::from_secs(60)));
+        let (key, policy) = cache.key("getBalance", &json!(["abc"])).unwrap();
+        let result = json!({ "context": { "slot": 10 }, "value": 1 });
+        cache.insert(key.clone(), policy, &result);
+
+        assert_eq!(cache.get(&key, None), Some(result.clone()));
+        assert_eq!(cache.get(&key, Some(10)), Some(result));
+        assert_eq!(cache.get(&key, Some(11)), None);
+    }
+
+    #[test]
+    fn ttl_entries_expire() {
+        let storage = LruStorage::new(NonZeroUsize::new(1).unwrap());
+        storage.insert("a".to_string(), json!(1), Some(Duration::ZERO));
+        assert_eq!(storage.get("a"), None);
+    }
+
+    #[test]
+    fn lru_evicts_least_recently_used() {
+        let storage = LruStorage::new(NonZeroUsize::new(2).unwrap());
+        storage.insert("a".to_string(), json!(1), None);
+        storage.insert("b".to_string(), json!(2), None);
+        storage.get("a");
+        storage.insert("c".to_string(), json!(3), None);
+        assert_eq!(storage.get("a"), Some(json!(1)));
+        assert_eq!(storage.get("b"), None);
+        assert_eq!(storage.get("c"), Some(json!(3)));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::error::{JsonRpcError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// One recorded request/response pair, stored as a line of JSON.
+///
+/// A JSON response is stored without its `id`, which is filled in from the
+/// replayed request so id validation keeps working. Any other body, such as
+/// the text of an HTTP error, is stored as a string.
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    method: String,
+    params: serde_json::Value,
+    /// HTTP status of the response.
+    status: u16,
+    response: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecordedRequest {
+    id: serde_json::Value,
+    method: String,
+    params: serde_json::Value,
+}
+
+/// Records live traffic to, or replays it from, a JSON lines file.
+///
+/// Attach one to a client with
+/// [`JsonRpcClient::with_cassette`](crate::JsonRpcClient::with_cassette).
+#[derive(Debug)]
+pub struct Cassette {
+    mode: Mode,
+}
+
+#[derive(Debug)]
+enum Mode {
+    Record(Mutex<File>),
+    Replay(Mutex<HashMap<String, VecDeque<(u16, serde_json::Value)>>>),
+}
+
+impl Cassette {
+    /// Sends requests to the endpoint as usual and appends every exchange,
+    /// including HTTP errors, to `path`, truncating any existing cassette.
+    pub fn record(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+
+        Ok(Self {
+            mode: Mode::Record(Mutex::new(file)),
+        })
+    }
+
+    /// Serves responses from `path` without touching the network.
+    ///
+    /// Identical requests are answered in the order they were recorded, with
+    /// the last response repeated once they run out. A request that was never
+    /// recorded fails with [`JsonRpcError::Cassette`].
+    pub fn replay(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut interactions: HashMap<_, VecDeque<_>> = HashMap::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let interaction: Interaction = serde_json::from_str(&line)?;
+            interactions
+                .entry(key(&interaction.method, &interaction.params))
+                .or_default()
+                .push_back((interaction.status, interaction.response));
+        }
+
+        Ok(Self {
+            mode: Mode::Replay(Mutex::new(interactions)),
+        })
+    }
+
+    /// Builds a cassette from the environment: `SOLANA_RPC_REPLAY=<path>`
+    /// replays from a file and `SOLANA_RPC_RECORD=<path>` records to one.
+    /// Returns `None` when neither is set.
+    pub fn from_env() -> std::io::Result<Option<Self>> {
+        if let Some(path) = std::env::var_os("SOLANA_RPC_REPLAY") {
+            return Self::replay(path).map(Some);
+        }
+        if let Some(path) = std::env::var_os("SOLANA_RPC_RECORD") {
+            return Self::record(path).map(Some);
+        }
+        Ok(None)
+    }
+
+    pub(crate) fn is_replay(&self) -> bool {
+        matches!(self.mode, Mode::Replay(_))
+    }
+
+    pub(crate) fn record_exchange(&self, request: &[u8], status: u16, response: &[u8]) -> Result<()> {
+        let Mode::Record(file) = &self.mode else {
+            return Ok(());
+        };
+
+        let request: RecordedRequest = serde_json::from_slice(request)?;
+        let response = match serde_json::from_slice(response) {
+            Ok(serde_json::Value::Object(mut response)) => {
+                response.remove("id");
+                serde_json::Value::Object(response)
+            }
+            _ => String::from_utf8_lossy(response).into_owned().into(),
+        };
+
+        let mut line = serde_json::to_vec(&Interaction {
+            method: request.method,
+            params: request.params,
+            status,
+            response,
+        })?;
+        line.push(b'\n');
+
+        let mut file = file.lock().unwrap();
+        file.write_all(&line)
+            .and_then(|_| file.flush())
+            .map_err(|err| JsonRpcError::Cassette(format!("failed to write cassette: {}", err)))
+    }
+
+    /// Returns the recorded status and body for `request`.
+    pub(crate) fn replay_exchange(&self, request: &[u8]) -> Result<(u16, Vec<u8>)> {
+        let Mode::Replay(interactions) = &self.mode else {
+            return Err(JsonRpcError::Cassette("cassette is not in replay mode".to_string()));
+        };
+
+        let request: RecordedRequest = serde_json::from_slice(request)?;
+        let key = key(&request.method, &request.params);
+
+        let mut interactions = interactions.lock().unwrap();
+        let recorded = interactions.get_mut(&key).filter(|queue| !queue.is_empty());
+        let Some(queue) = recorded else {
+            return Err(JsonRpcError::Cassette(format!(
+                "no recorded response for {} with params {}",
+                request.method, request.params
+            )));
+        };
+
+        let (status, response) = if queue.len() > 1 {
+            queue.pop_front().unwrap()
+        } else {
+            queue[0].clone()
+        };
+        let body = match response {
+            serde_json::Value::Object(mut response) => {
+                response.insert("id".to_string(), request.id);
+                serde_json::to_vec(&response)?
+            }
+            serde_json::Value::String(text) => text.into_bytes(),
+            other => serde_json::to_vec(&other)?,
+        };
+        Ok((status, body))
+    }
+}
+
+fn key(method: &str, params: &serde_json::Value) -> String {
+    format!("{}:{}", method, params)
+}
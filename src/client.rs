@@ -1,37 +1,53 @@
+use crate::cache::{self, ResponseCache};
+use crate::cassette::Cassette;
+use crate::coalesce::{Coalescer, CoalescingStats};
 use crate::error::{JsonRpcError, Result};
+use crate::id::{IdGenerator, RequestId, SequentialIds};
+#[cfg(feature = "instrumentation")]
+use crate::instrument;
+use crate::limit::{RatePermit, RateLimiter};
+use crate::types::RpcResponse;
 use serde::{Deserialize, Serialize};
-use std::sync::atomic::{AtomicU64, Ordering};
-
-static REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+use serde_json::value::RawValue;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
 
 #[derive(Debug, Clone)]
 pub struct JsonRpcClient {
     url: String,
     client: reqwest::Client,
+    cache: Option<Arc<ResponseCache>>,
+    coalescer: Arc<Coalescer>,
+    limiter: Option<Arc<RateLimiter>>,
+    ids: Arc<dyn IdGenerator>,
+    max_response_size: Option<usize>,
+    cassette: Option<Arc<Cassette>>,
 }
 
 #[derive(Debug, Serialize)]
 struct JsonRpcRequest {
     jsonrpc: String,
-    id: u64,
+    id: RequestId,
     method: String,
     params: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(bound(deserialize = "T: Deserialize<'de>"))]
 struct JsonRpcResponse<T> {
     #[allow(dead_code)]
     jsonrpc: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_present")]
     result: Option<T>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<JsonRpcErrorResponse>,
-    #[allow(dead_code)]
-    id: u64,
+    id: Option<RequestId>,
 }
 
 #[derive(Debug, Deserialize)]
-struct JsonRpcErrorResponse {
+pub(crate) struct JsonRpcErrorResponse {
     code: i64,
     message: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -39,9 +55,58 @@ struct JsonRpcErrorResponse {
     data: Option<serde_json::Value>,
 }
 
+// Distinguishes `"result": null` from a missing `result` field, so that methods
+// which legitimately return `null` (e.g. `getBlock` for a skipped slot) don't
+// surface as `MissingResult`.
+fn deserialize_present<'de, T, D>(deserializer: D) -> std::result::Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Per-call limits for [`JsonRpcClient::call_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct CallOptions {
+    pub deadline: Option<Instant>,
+    pub cancel: Option<CancellationToken>,
+}
+
+impl CallOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn with_cancellation(mut self, cancel: CancellationToken) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+}
+
 pub trait RpcRequest: Serialize {
     fn method_name() -> &'static str;
-    type Response: for<'de> Deserialize<'de>;
+    type Response: RpcResponse;
+
+    /// Whether the method wraps its result in `{"context": ..., "value": ...}`.
+    fn has_context(&self) -> bool {
+        false
+    }
+
+    /// The request's `min_context_slot` setting, for methods that accept one.
+    /// Used by [`Session`](crate::Session) to keep reads monotonic.
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        None
+    }
 }
 
 impl JsonRpcClient {
@@ -49,49 +114,399 @@ impl JsonRpcClient {
         Self {
             url: url.into(),
             client: reqwest::Client::new(),
+            cache: None,
+            coalescer: Arc::default(),
+            limiter: None,
+            ids: Arc::new(SequentialIds::default()),
+            max_response_size: None,
+            cassette: None,
         }
     }
 
+    /// Uses `ids` to generate request ids instead of counting up from 1.
+    pub fn with_id_generator(mut self, ids: impl IdGenerator + 'static) -> Self {
+        self.ids = Arc::new(ids);
+        self
+    }
+
+    /// Fails calls whose response body is larger than `bytes` with
+    /// [`JsonRpcError::ResponseTooLarge`] instead of buffering it. Use
+    /// [`JsonRpcClient::stream_program_accounts`] for responses that are
+    /// expected to be huge.
+    pub fn with_max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = Some(bytes);
+        self
+    }
+
+    /// Records every exchange to, or replays them from, `cassette`.
+    pub fn with_cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(Arc::new(cassette));
+        self
+    }
+
+    /// Attaches the cassette configured in the environment, if any. See
+    /// [`Cassette::from_env`].
+    pub fn with_cassette_from_env(self) -> std::io::Result<Self> {
+        Ok(match Cassette::from_env()? {
+            Some(cassette) => self.with_cassette(cassette),
+            None => self,
+        })
+    }
+
+    /// Serves responses from `cache` according to its per-method policies.
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(Arc::new(cache));
+        self
+    }
+
+    pub fn cache(&self) -> Option<&ResponseCache> {
+        self.cache.as_deref()
+    }
+
+    /// Makes calls wait for `limiter` before they are sent. Clones of this
+    /// client share the same limits.
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.limiter = Some(Arc::new(limiter));
+        self
+    }
+
     pub async fn call<R>(&self, request: R) -> Result<R::Response>
     where
         R: RpcRequest,
     {
-        let id = REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+        let method = R::method_name();
+        self.instrumented(method, async {
+            let params = serde_json::to_value(&request)?;
+            self.execute(method, params, request.has_context()).await
+        })
+        .await
+    }
+
+    /// Like [`JsonRpcClient::call`], but gives up with
+    /// [`JsonRpcError::Timeout`] once `options.deadline` passes, or with
+    /// [`JsonRpcError::Cancelled`] once `options.cancel` is cancelled. The
+    /// deadline covers the whole call, including time spent waiting on the
+    /// rate limiter.
+    pub async fn call_with_options<R>(&self, request: R, options: CallOptions) -> Result<R::Response>
+    where
+        R: RpcRequest,
+    {
+        let method = R::method_name();
+        let started = Instant::now();
+
+        let deadline = async {
+            match options.deadline {
+                Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+                None => std::future::pending().await,
+            }
+        };
+        let cancelled = async {
+            match &options.cancel {
+                Some(cancel) => cancel.cancelled().await,
+                None => std::future::pending().await,
+            }
+        };
+
+        let call = async {
+            let params = serde_json::to_value(&request)?;
+            self.execute(method, params, request.has_context()).await
+        };
+
+        self.instrumented(method, async {
+            tokio::select! {
+                result = call => result,
+                _ = deadline => Err(JsonRpcError::Timeout {
+                    method: method.to_string(),
+                    elapsed: started.elapsed(),
+                }),
+                _ = cancelled => Err(JsonRpcError::Cancelled {
+                    method: method.to_string(),
+                }),
+            }
+        })
+        .await
+    }
+
+    /// Like [`JsonRpcClient::call`], but identical requests (same method and
+    /// params) made while one is already in flight wait for and share its
+    /// response instead of sending their own.
+    ///
+    /// The call that sent the request gets its error as is. Calls that joined
+    /// it get the error as [`JsonRpcError::Shared`]; use
+    /// [`JsonRpcError::root`] to match on it. Network and deserialization
+    /// errors cannot be copied, so the sending call also gets those as
+    /// `Shared` when other calls joined it.
+    pub async fn call_coalesced<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: RpcRequest,
+        R::Response: Clone + Send + Sync + 'static,
+    {
+        let method = R::method_name();
+        let with_context = request.has_context();
         let params = serde_json::to_value(&request)?;
+        let key = format!("{}:{}", method, params);
+
+        let slot = self.coalescer.join::<R::Response>(&key);
+        let mut sent = false;
+        let mut own_error = None;
+        let result = slot
+            .get_or_init(|| async {
+                sent = true;
+                let result = self.instrumented(method, self.execute(method, params, with_context)).await;
+                self.coalescer.finish(&key, &slot);
+                result.map_err(|err| match err.try_clone() {
+                    Some(copy) => {
+                        own_error = Some(err);
+                        Arc::new(copy)
+                    }
+                    None => Arc::new(err),
+                })
+            })
+            .await
+            .clone();
+
+        if let Some(err) = own_error {
+            return Err(err);
+        }
+        match result {
+            Ok(response) => Ok(response),
+            // Once every other handle is gone the error is ours alone.
+            Err(err) if sent => {
+                drop(slot);
+                Err(Arc::try_unwrap(err).unwrap_or_else(JsonRpcError::Shared))
+            }
+            Err(err) => Err(JsonRpcError::Shared(err)),
+        }
+    }
+
+    pub fn coalescing_stats(&self) -> CoalescingStats {
+        self.coalescer.stats()
+    }
+
+    // Instruments the whole call rather than the request alone, so that
+    // errors raised around it, such as timeouts, are recorded too.
+    #[cfg_attr(not(feature = "instrumentation"), allow(unused_variables))]
+    pub(crate) async fn instrumented<T, F>(&self, method: &str, call: F) -> Result<T>
+    where
+        T: RpcResponse,
+        F: Future<Output = Result<T>>,
+    {
+        #[cfg(feature = "instrumentation")]
+        return instrument::instrumented(method, &self.url, call).await;
+
+        #[cfg(not(feature = "instrumentation"))]
+        call.await
+    }
+
+    async fn execute<T>(&self, method: &str, params: serde_json::Value, with_context: bool) -> Result<T>
+    where
+        T: RpcResponse,
+    {
+        let cache = self
+            .cache
+            .as_deref()
+            .and_then(|cache| Some((cache, cache.key(method, &params)?)));
+
+        match cache {
+            Some((cache, (key, policy))) => {
+                if let Some(result) = cache.get(&key, cache::min_context_slot(&params)) {
+                    return Ok(T::from_result(result, with_context)?);
+                }
+                let (id, body) = self.send(method, params).await?;
+                let result = response_result(&id, &body)?;
+                cache.insert(key, policy, &serde_json::from_str(result.get())?);
+                Ok(T::from_result(result, with_context)?)
+            }
+            None => {
+                let (id, body) = self.send(method, params).await?;
+                Ok(T::from_result(response_result(&id, &body)?, with_context)?)
+            }
+        }
+    }
+
+    // Returns the request id and the response body.
+    async fn send(&self, method: &str, params: serde_json::Value) -> Result<(RequestId, Vec<u8>)> {
+        let _permit = self.permit(method).await;
+        let (id, body) = self.encode(method, params)?;
+
+        #[cfg(feature = "instrumentation")]
+        instrument::sent(method, &id, body.len());
+
+        let response = self.post(body, self.max_response_size).await?;
+        let body = self.read_body(response).await?;
+
+        #[cfg(feature = "instrumentation")]
+        instrument::received(method, body.len());
+
+        Ok((id, body))
+    }
+
+    #[cfg(feature = "instrumentation")]
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub(crate) async fn permit(&self, method: &str) -> Option<RatePermit> {
+        match &self.limiter {
+            Some(limiter) => Some(limiter.acquire(method).await),
+            None => None,
+        }
+    }
+
+    pub(crate) fn encode(&self, method: &str, params: serde_json::Value) -> Result<(RequestId, Vec<u8>)> {
+        let id = self.ids.next_id();
         let jsonrpc_request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
-            id,
-            method: R::method_name().to_string(),
+            id: id.clone(),
+            method: method.to_string(),
             params,
         };
+        let body = serde_json::to_vec(&jsonrpc_request)?;
+        Ok((id, body))
+    }
+
+    /// Posts `body`, through the cassette if there is one. `max_size` caps
+    /// how much of a response may be buffered when recording it.
+    pub(crate) async fn post(&self, body: Vec<u8>, max_size: Option<usize>) -> Result<reqwest::Response> {
+        let Some(cassette) = &self.cassette else {
+            return check_status(self.post_http(body).await?);
+        };
+
+        let (status, response) = if cassette.is_replay() {
+            cassette.replay_exchange(&body)?
+        } else {
+            let response = self.post_http(body.clone()).await?;
+            let status = response.status().as_u16();
+            let recorded = read_limited(response, max_size).await?;
+            cassette.record_exchange(&body, status, &recorded)?;
+            (status, recorded)
+        };
+
+        let response = http::Response::builder()
+            .status(status)
+            .body(response)
+            .map_err(|err| JsonRpcError::Cassette(err.to_string()))?;
+        check_status(response.into())
+    }
 
+    async fn post_http(&self, body: Vec<u8>) -> Result<reqwest::Response> {
         let response = self
             .client
             .post(&self.url)
-            .json(&jsonrpc_request)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
             .send()
             .await?;
+        Ok(response)
+    }
 
-        let status = response.status();
-        if !status.is_success() {
-            return Err(JsonRpcError::InvalidResponse(format!(
-                "HTTP error: {}",
-                status
-            )));
-        }
+    async fn read_body(&self, response: reqwest::Response) -> Result<Vec<u8>> {
+        read_limited(response, self.max_response_size).await
+    }
+}
 
-        let jsonrpc_response: JsonRpcResponse<R::Response> = response.json().await?;
+// The raw `result` of a response body, once its id and error are checked.
+fn response_result<'a>(id: &RequestId, body: &'a [u8]) -> Result<&'a RawValue> {
+    let response: JsonRpcResponse<&RawValue> = serde_json::from_slice(body)?;
+    check_response(id, response.id, response.error)?;
+    response.result.ok_or(JsonRpcError::MissingResult)
+}
+
+fn check_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(JsonRpcError::InvalidResponse(format!(
+            "HTTP error: {}",
+            status
+        )));
+    }
+    Ok(response)
+}
 
-        if let Some(error) = jsonrpc_response.error {
-            return Err(JsonRpcError::Rpc {
-                code: error.code,
-                message: error.message,
-            });
+async fn read_limited(mut response: reqwest::Response, limit: Option<usize>) -> Result<Vec<u8>> {
+    let Some(limit) = limit else {
+        return Ok(response.bytes().await?.to_vec());
+    };
+
+    if response.content_length().is_some_and(|len| len > limit as u64) {
+        return Err(JsonRpcError::ResponseTooLarge { limit });
+    }
+
+    // The declared length can be missing or wrong, so count as we read.
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(JsonRpcError::ResponseTooLarge { limit });
         }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
 
-        jsonrpc_response
-            .result
-            .ok_or(JsonRpcError::MissingResult)
+// Servers answer with a `null` id when they could not read the request's id at
+// all, in which case the error is what matters.
+pub(crate) fn check_response(
+    id: &RequestId,
+    response_id: Option<RequestId>,
+    error: Option<JsonRpcErrorResponse>,
+) -> Result<()> {
+    let unidentified_error = response_id.is_none() && error.is_some();
+    if response_id.as_ref() != Some(id) && !unidentified_error {
+        return Err(JsonRpcError::IdMismatch {
+            expected: id.clone(),
+            actual: response_id,
+        });
     }
+
+    if let Some(error) = error {
+        return Err(JsonRpcError::Rpc {
+            code: error.code,
+            message: error.message,
+        });
+    }
+
+    Ok(())
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpc_error() -> Option<JsonRpcErrorResponse> {
+        Some(JsonRpcErrorResponse {
+            code: -32600,
+            message: "Invalid request".to_string(),
+            data: None,
+        })
+    }
+
+    #[test]
+    fn accepts_matching_id() {
+        let id = RequestId::from(1);
+        assert!(check_response(&id, Some(RequestId::from(1)), None).is_ok());
+    }
+
+    #[test]
+    fn rejects_other_ids() {
+        let id = RequestId::from(1);
+        let err = check_response(&id, Some(RequestId::from("1")), None).unwrap_err();
+        assert!(matches!(err, JsonRpcError::IdMismatch { actual: Some(_), .. }));
+
+        let err = check_response(&id, None, None).unwrap_err();
+        assert!(matches!(err, JsonRpcError::IdMismatch { actual: None, .. }));
+    }
+
+    #[test]
+    fn null_id_reports_the_error() {
+        let id = RequestId::from(1);
+        let err = check_response(&id, None, rpc_error()).unwrap_err();
+        assert!(matches!(err, JsonRpcError::Rpc { code: -32600, .. }));
+    }
+
+    #[test]
+    fn mismatch_takes_precedence_over_error() {
+        let id = RequestId::from(1);
+        let err = check_response(&id, Some(RequestId::from(2)), rpc_error()).unwrap_err();
+        assert!(matches!(err, JsonRpcError::IdMismatch { .. }));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::error::JsonRpcError;
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+pub(crate) type SharedResult<T> = std::result::Result<T, Arc<JsonRpcError>>;
+
+type InFlight = Arc<dyn Any + Send + Sync>;
+
+/// Counters describing how effective request coalescing has been.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CoalescingStats {
+    /// Calls made through [`JsonRpcClient::call_coalesced`](crate::JsonRpcClient::call_coalesced).
+    pub calls: u64,
+    /// Calls that were answered by an identical request already in flight
+    /// instead of sending their own.
+    pub coalesced: u64,
+}
+
+/// Tracks requests in flight, keyed on method and serialized params.
+#[derive(Debug, Default)]
+pub(crate) struct Coalescer {
+    in_flight: Mutex<HashMap<String, InFlight>>,
+    calls: AtomicU64,
+    coalesced: AtomicU64,
+}
+
+impl Coalescer {
+    /// Returns the shared slot for `key`, creating it if no identical request
+    /// is in flight.
+    pub(crate) fn join<T>(&self, key: &str) -> Arc<OnceCell<SharedResult<T>>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+
+        let mut in_flight = self.in_flight.lock().unwrap();
+        let existing = in_flight
+            .get(key)
+            .and_then(|slot| slot.clone().downcast::<OnceCell<SharedResult<T>>>().ok());
+
+        match existing {
+            Some(slot) => {
+                self.coalesced.fetch_add(1, Ordering::Relaxed);
+                slot
+            }
+            None => {
+                let slot = Arc::new(OnceCell::new());
+                in_flight.insert(key.to_string(), slot.clone() as InFlight);
+                slot
+            }
+        }
+    }
+
+    /// Forgets the slot for `key` once its response has arrived, so later
+    /// calls hit the network again.
+    pub(crate) fn finish<T>(&self, key: &str, slot: &Arc<OnceCell<SharedResult<T>>>)
+    where
+        T: Send + Sync + 'static,
+    {
+        let mut in_flight = self.in_flight.lock().unwrap();
+        let slot = slot.clone() as InFlight;
+        if in_flight.get(key).is_some_and(|current| Arc::ptr_eq(current, &slot)) {
+            in_flight.remove(key);
+        }
+    }
+
+    pub(crate) fn stats(&self) -> CoalescingStats {
+        CoalescingStats {
+            calls: self.calls.load(Ordering::Relaxed),
+            coalesced: self.coalesced.load(Ordering::Relaxed),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use crate::client::JsonRpcClient;
+use crate::error::{JsonRpcError, Result};
+use crate::filter::validate_filters;
+use crate::methods::account::{AccountInfoConfig, RpcGetAccountInfoRequest};
+use crate::methods::program::{ProgramAccountFilter, ProgramAccountsConfig, RpcGetProgramAccountsRequest};
+use crate::types::{AccountDataError, AccountInfo, Encoding};
+use thiserror::Error;
+
+/// A type that can be decoded from raw account data.
+///
+/// Accounts of types with a non-empty [`DISCRIMINATOR`](Self::DISCRIMINATOR)
+/// must start with it; it is checked and stripped before
+/// [`deserialize_account`](Self::deserialize_account) sees the data.
+pub trait AccountDeserialize: Sized {
+    const DISCRIMINATOR: &'static [u8] = &[];
+
+    fn deserialize_account(data: &[u8]) -> std::result::Result<Self, AccountDeserializeError>;
+}
+
+#[derive(Error, Debug)]
+pub enum AccountDeserializeError {
+    #[error(transparent)]
+    Data(#[from] AccountDataError),
+
+    #[error("Account data is {actual} bytes, expected {expected}")]
+    Size { expected: usize, actual: usize },
+
+    #[error("Account discriminator {actual:?} does not match {expected:?}")]
+    Discriminator { expected: Vec<u8>, actual: Vec<u8> },
+
+    #[error("Invalid account data: {0}")]
+    Invalid(String),
+}
+
+/// Checks `T::DISCRIMINATOR` and decodes the rest of `data` as `T`.
+pub fn decode_account<T: AccountDeserialize>(data: &[u8]) -> std::result::Result<T, AccountDeserializeError> {
+    T::deserialize_account(strip_discriminator(data, T::DISCRIMINATOR)?)
+}
+
+/// Returns the data following `expected`, or an error if `data` does not
+/// start with it.
+pub fn strip_discriminator<'a>(
+    data: &'a [u8],
+    expected: &[u8],
+) -> std::result::Result<&'a [u8], AccountDeserializeError> {
+    if data.len() < expected.len() {
+        return Err(AccountDeserializeError::Size {
+            expected: expected.len(),
+            actual: data.len(),
+        });
+    }
+
+    let (discriminator, rest) = data.split_at(expected.len());
+    if discriminator != expected {
+        return Err(AccountDeserializeError::Discriminator {
+            expected: expected.to_vec(),
+            actual: discriminator.to_vec(),
+        });
+    }
+    Ok(rest)
+}
+
+impl AccountInfo {
+    /// Decodes the account data as `T`. See [`decode_account`].
+    pub fn decode_as<T: AccountDeserialize>(&self) -> std::result::Result<T, AccountDeserializeError> {
+        decode_account(&self.data_bytes()?)
+    }
+}
+
+/// Decodes a Borsh-serialized account. Trailing bytes, such as the padding
+/// of an account allocated larger than its contents, are ignored.
+#[cfg(feature = "borsh")]
+pub fn deserialize_borsh<T: borsh::BorshDeserialize>(data: &[u8]) -> std::result::Result<T, AccountDeserializeError> {
+    T::deserialize(&mut &data[..]).map_err(|err| AccountDeserializeError::Invalid(err.to_string()))
+}
+
+/// Reads a fixed-layout account. The data must be at least
+/// `size_of::<T>()` bytes; it need not be aligned.
+#[cfg(feature = "bytemuck")]
+pub fn deserialize_pod<T: bytemuck::Pod>(data: &[u8]) -> std::result::Result<T, AccountDeserializeError> {
+    let size = std::mem::size_of::<T>();
+    if data.len() < size {
+        return Err(AccountDeserializeError::Size {
+            expected: size,
+            actual: data.len(),
+        });
+    }
+    Ok(bytemuck::pod_read_unaligned(&data[..size]))
+}
+
+/// Decodes any [`borsh::BorshDeserialize`] type as an account without a
+/// discriminator.
+#[cfg(feature = "borsh")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Borsh<T>(pub T);
+
+#[cfg(feature = "borsh")]
+impl<T: borsh::BorshDeserialize> AccountDeserialize for Borsh<T> {
+    fn deserialize_account(data: &[u8]) -> std::result::Result<Self, AccountDeserializeError> {
+        deserialize_borsh(data).map(Borsh)
+    }
+}
+
+/// Decodes any [`bytemuck::Pod`] type as an account without a
+/// discriminator.
+#[cfg(feature = "bytemuck")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pod<T>(pub T);
+
+#[cfg(feature = "bytemuck")]
+impl<T: bytemuck::Pod> AccountDeserialize for Pod<T> {
+    fn deserialize_account(data: &[u8]) -> std::result::Result<Self, AccountDeserializeError> {
+        deserialize_pod(data).map(Pod)
+    }
+}
+
+impl JsonRpcClient {
+    /// Fetches an account and decodes its data as `T`. Returns `None` when
+    /// the account does not exist.
+    pub async fn get_account_as<T: AccountDeserialize>(&self, pubkey: &str) -> Result<Option<T>> {
+        let response = self
+            .call(RpcGetAccountInfoRequest {
+                pubkey: pubkey.to_string(),
+                config: Some(AccountInfoConfig {
+                    encoding: Some(Encoding::Base64),
+                    ..Default::default()
+                }),
+            })
+            .await?;
+
+        response
+            .into_value()
+            .map(|info| decode(pubkey, &info))
+            .transpose()
+    }
+
+    /// Fetches the accounts owned by `program` that match `filters` and
+    /// decodes each as `T`. When `T` has a discriminator, only accounts
+    /// starting with it are requested; it counts towards the node's limit of
+    /// [`MAX_FILTERS`](crate::filter::MAX_FILTERS).
+    pub async fn get_program_accounts_as<T: AccountDeserialize>(
+        &self,
+        program: &str,
+        mut filters: Vec<ProgramAccountFilter>,
+    ) -> Result<Vec<(String, T)>> {
+        if !T::DISCRIMINATOR.is_empty() {
+            filters.insert(0, ProgramAccountFilter::discriminator(T::DISCRIMINATOR));
+        }
+        validate_filters(&filters)?;
+
+        let response = self
+            .call(RpcGetProgramAccountsRequest {
+                program_id: program.to_string(),
+                config: Some(ProgramAccountsConfig {
+                    encoding: Some(Encoding::Base64),
+                    filters: (!filters.is_empty()).then_some(filters),
+                    ..Default::default()
+                }),
+            })
+            .await?;
+
+        response
+            .into_value()
+            .into_iter()
+            .map(|account| Ok((account.pubkey.clone(), decode(&account.pubkey, &account.account)?)))
+            .collect()
+    }
+}
+
+fn decode<T: AccountDeserialize>(pubkey: &str, info: &AccountInfo) -> Result<T> {
+    info.decode_as().map_err(|source| JsonRpcError::AccountDecode {
+        pubkey: pubkey.to_string(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Counter(u64);
+
+    impl AccountDeserialize for Counter {
+        const DISCRIMINATOR: &'static [u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+        fn deserialize_account(data: &[u8]) -> std::result::Result<Self, AccountDeserializeError> {
+            let bytes = data.get(..8).ok_or(AccountDeserializeError::Size {
+                expected: 8,
+                actual: data.len(),
+            })?;
+            Ok(Counter(u64::from_le_bytes(bytes.try_into().unwrap())))
+        }
+    }
+
+    fn counter_data(discriminator: &[u8], count: u64) -> Vec<u8> {
+        [discriminator, &count.to_le_bytes()].concat()
+    }
+
+    #[test]
+    fn strips_a_matching_discriminator() {
+        assert_eq!(strip_discriminator(&[1, 2, 3, 4], &[1, 2]).unwrap(), &[3, 4]);
+        assert_eq!(strip_discriminator(&[1, 2], &[]).unwrap(), &[1, 2]);
+        assert!(strip_discriminator(&[1, 2], &[1, 2]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_short_or_mismatched_discriminators() {
+        assert!(matches!(
+            strip_discriminator(&[1], &[1, 2]),
+            Err(AccountDeserializeError::Size { expected: 2, actual: 1 })
+        ));
+        match strip_discriminator(&[9, 2, 3], &[1, 2]) {
+            Err(AccountDeserializeError::Discriminator { expected, actual }) => {
+                assert_eq!(expected, [1, 2]);
+                assert_eq!(actual, [9, 2]);
+            }
+            other => panic!("expected a discriminator error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn decodes_after_the_discriminator() {
+        let data = counter_data(Counter::DISCRIMINATOR, 42);
+        assert_eq!(decode_account::<Counter>(&data).unwrap(), Counter(42));
+
+        let wrong = counter_data(&[0; 8], 42);
+        assert!(matches!(
+            decode_account::<Counter>(&wrong),
+            Err(AccountDeserializeError::Discriminator { .. })
+        ));
+        assert!(matches!(
+            decode_account::<Counter>(Counter::DISCRIMINATOR),
+            Err(AccountDeserializeError::Size { expected: 8, actual: 0 })
+        ));
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn decodes_borsh_and_ignores_trailing_bytes() {
+        let mut data = 7u64.to_le_bytes().to_vec();
+        data.extend([3, 0, 0, 0, b'a', b'b', b'c']);
+        data.extend([0; 16]);
+
+        let Borsh((count, name)) = decode_account::<Borsh<(u64, String)>>(&data).unwrap();
+        assert_eq!(count, 7);
+        assert_eq!(name, "abc");
+
+        assert!(matches!(
+            deserialize_borsh::<(u64, String)>(&data[..10]),
+            Err(AccountDeserializeError::Invalid(_))
+        ));
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn reads_unaligned_pod_data() {
+        let mut data = vec![0u8];
+        data.extend(1u32.to_le_bytes());
+        data.extend(2u32.to_le_bytes());
+
+        let Pod(fields) = decode_account::<Pod<[u32; 2]>>(&data[1..]).unwrap();
+        assert_eq!(fields, [1, 2]);
+
+        assert!(matches!(
+            deserialize_pod::<u64>(&data[..4]),
+            Err(AccountDeserializeError::Size { expected: 8, actual: 4 })
+        ));
+    }
+}
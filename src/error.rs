@@ -1,3 +1,8 @@
+use crate::deserialize::AccountDeserializeError;
+use crate::filter::FilterError;
+use crate::id::RequestId;
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -19,6 +24,104 @@ pub enum JsonRpcError {
 
     #[error("Missing result in response")]
     MissingResult,
+
+    #[error(
+        "Response id {} does not match request id {expected}",
+        .actual.as_ref().map_or("null".to_string(), ToString::to_string)
+    )]
+    IdMismatch {
+        expected: RequestId,
+        actual: Option<RequestId>,
+    },
+
+    #[error("Response exceeds the maximum size of {limit} bytes")]
+    ResponseTooLarge { limit: usize },
+
+    #[error("{method} timed out after {elapsed:?}")]
+    Timeout { method: String, elapsed: Duration },
+
+    #[error("{method} was cancelled")]
+    Cancelled { method: String },
+
+    #[error("Cassette error: {0}")]
+    Cassette(String),
+
+    #[error("Failed to decode account {pubkey}: {source}")]
+    AccountDecode {
+        pubkey: String,
+        #[source]
+        source: AccountDeserializeError,
+    },
+
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(#[from] FilterError),
+
+    #[cfg(feature = "idl")]
+    #[error("IDL error: {0}")]
+    Idl(#[from] crate::anchor::idl::IdlError),
+
+    #[error("{0}")]
+    Shared(Arc<JsonRpcError>),
+}
+
+impl JsonRpcError {
+    /// Short, stable name of the error variant, suitable as a metric label.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            JsonRpcError::Network(_) => "network",
+            JsonRpcError::Rpc { .. } => "rpc",
+            JsonRpcError::Deserialization(_) => "deserialization",
+            JsonRpcError::InvalidUrl(_) => "invalid_url",
+            JsonRpcError::InvalidResponse(_) => "invalid_response",
+            JsonRpcError::MissingResult => "missing_result",
+            JsonRpcError::IdMismatch { .. } => "id_mismatch",
+            JsonRpcError::ResponseTooLarge { .. } => "response_too_large",
+            JsonRpcError::Timeout { .. } => "timeout",
+            JsonRpcError::Cancelled { .. } => "cancelled",
+            JsonRpcError::Cassette(_) => "cassette",
+            JsonRpcError::AccountDecode { .. } => "account_decode",
+            JsonRpcError::InvalidFilter(_) => "invalid_filter",
+            #[cfg(feature = "idl")]
+            JsonRpcError::Idl(_) => "idl",
+            JsonRpcError::Shared(err) => err.kind(),
+        }
+    }
+
+    /// The underlying error, looking through [`JsonRpcError::Shared`].
+    pub fn root(&self) -> &JsonRpcError {
+        match self {
+            JsonRpcError::Shared(err) => err.root(),
+            err => err,
+        }
+    }
+
+    /// A copy of the error, for the variants whose contents can be cloned.
+    pub(crate) fn try_clone(&self) -> Option<JsonRpcError> {
+        let err = match self {
+            JsonRpcError::Rpc { code, message } => JsonRpcError::Rpc {
+                code: *code,
+                message: message.clone(),
+            },
+            JsonRpcError::InvalidUrl(err) => JsonRpcError::InvalidUrl(*err),
+            JsonRpcError::InvalidResponse(message) => JsonRpcError::InvalidResponse(message.clone()),
+            JsonRpcError::MissingResult => JsonRpcError::MissingResult,
+            JsonRpcError::IdMismatch { expected, actual } => JsonRpcError::IdMismatch {
+                expected: expected.clone(),
+                actual: actual.clone(),
+            },
+            JsonRpcError::ResponseTooLarge { limit } => JsonRpcError::ResponseTooLarge { limit: *limit },
+            JsonRpcError::Timeout { method, elapsed } => JsonRpcError::Timeout {
+                method: method.clone(),
+                elapsed: *elapsed,
+            },
+            JsonRpcError::Cancelled { method } => JsonRpcError::Cancelled { method: method.clone() },
+            JsonRpcError::Cassette(message) => JsonRpcError::Cassette(message.clone()),
+            JsonRpcError::InvalidFilter(err) => JsonRpcError::InvalidFilter(err.clone()),
+            JsonRpcError::Shared(err) => JsonRpcError::Shared(err.clone()),
+            _ => return None,
+        };
+        Some(err)
+    }
 }
 
 pub type Result<T> = std::result::Result<T, JsonRpcError>;
@@ -0,0 +1,264 @@
+use crate::methods::program::{MemcmpEncoding, MemcmpFilter, ProgramAccountFilter};
+use thiserror::Error;
+
+/// The most filters a node accepts in one `getProgramAccounts` request.
+pub const MAX_FILTERS: usize = 4;
+
+/// The most bytes a node accepts in one memcmp filter.
+pub const MAX_MEMCMP_BYTES: usize = 128;
+
+/// Size of an SPL Token account. Token-2022 accounts with extensions are
+/// larger.
+pub const TOKEN_ACCOUNT_SIZE: u64 = 165;
+
+const TOKEN_ACCOUNT_MINT_OFFSET: usize = 0;
+const TOKEN_ACCOUNT_OWNER_OFFSET: usize = 32;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FilterError {
+    #[error("{count} filters exceed the limit of {MAX_FILTERS}")]
+    TooManyFilters { count: usize },
+
+    #[error("memcmp at offset {offset} compares {len} bytes, more than the limit of {MAX_MEMCMP_BYTES}")]
+    MemcmpTooLarge { offset: usize, len: usize },
+
+    #[error("memcmp at offset {offset} has invalid bytes")]
+    InvalidBytes { offset: usize },
+
+    #[error("Invalid pubkey: {0}")]
+    InvalidPubkey(String),
+}
+
+/// Checks `filters` against the limits nodes enforce, so a request that
+/// would be rejected fails before it is sent.
+///
+/// Filters that nodes accept but that can never match are not caught: a
+/// `DataSize` with a memcmp reaching past that size, for example. This is
+/// easy to get with Token-2022, whose accounts with extensions are larger
+/// than [`TOKEN_ACCOUNT_SIZE`], so the `token_accounts_for_*` presets skip
+/// them.
+pub fn validate_filters(filters: &[ProgramAccountFilter]) -> Result<(), FilterError> {
+    if filters.len() > MAX_FILTERS {
+        return Err(FilterError::TooManyFilters { count: filters.len() });
+    }
+
+    for filter in filters {
+        if let ProgramAccountFilter::Memcmp(memcmp) = filter {
+            let bytes = memcmp
+                .decoded_bytes()
+                .ok_or(FilterError::InvalidBytes { offset: memcmp.offset })?;
+            if bytes.len() > MAX_MEMCMP_BYTES {
+                return Err(FilterError::MemcmpTooLarge {
+                    offset: memcmp.offset,
+                    len: bytes.len(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+    Memcmp { offset: usize, bytes: Vec<u8> },
+    Filter(ProgramAccountFilter),
+}
+
+/// Builds `getProgramAccounts` filters from typed values.
+///
+/// Memcmp bytes are encoded when the filters are built, as base64 unless
+/// [`Filters::with_encoding`] says otherwise, and always carry an explicit
+/// `encoding`. [`Filters::build`] checks the result against the node's
+/// limits.
+#[derive(Debug, Clone)]
+pub struct Filters {
+    entries: Vec<Entry>,
+    encoding: MemcmpEncoding,
+    error: Option<FilterError>,
+}
+
+impl Default for Filters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Filters {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            encoding: MemcmpEncoding::Base64,
+            error: None,
+        }
+    }
+
+    /// SPL Token accounts holding `mint`. Token-2022 accounts with
+    /// extensions do not match; see [`validate_filters`].
+    pub fn token_accounts_for_mint(mint: &str) -> Self {
+        Self::new()
+            .data_size(TOKEN_ACCOUNT_SIZE)
+            .memcmp_pubkey(TOKEN_ACCOUNT_MINT_OFFSET, mint)
+    }
+
+    /// SPL Token accounts owned by `owner`. Token-2022 accounts with
+    /// extensions do not match; see [`validate_filters`].
+    pub fn token_accounts_for_owner(owner: &str) -> Self {
+        Self::new()
+            .data_size(TOKEN_ACCOUNT_SIZE)
+            .memcmp_pubkey(TOKEN_ACCOUNT_OWNER_OFFSET, owner)
+    }
+
+    /// Encoding used for the bytes of every memcmp filter.
+    pub fn with_encoding(mut self, encoding: MemcmpEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    pub fn data_size(mut self, size: u64) -> Self {
+        self.entries.push(Entry::Filter(ProgramAccountFilter::DataSize(size)));
+        self
+    }
+
+    /// Matches initialized token accounts, whatever their extensions.
+    pub fn token_account_state(mut self) -> Self {
+        self.entries.push(Entry::Filter(ProgramAccountFilter::TokenAccountState));
+        self
+    }
+
+    pub fn memcmp_bytes(mut self, offset: usize, bytes: &[u8]) -> Self {
+        self.entries.push(Entry::Memcmp {
+            offset,
+            bytes: bytes.to_vec(),
+        });
+        self
+    }
+
+    /// Matches a base58 pubkey at `offset`. An invalid pubkey is reported
+    /// by [`Filters::build`].
+    pub fn memcmp_pubkey(self, offset: usize, pubkey: &str) -> Self {
+        match bs58::decode(pubkey).into_vec() {
+            Ok(bytes) if bytes.len() == 32 => self.memcmp_bytes(offset, &bytes),
+            _ => self.fail(FilterError::InvalidPubkey(pubkey.to_string())),
+        }
+    }
+
+    /// Matches a little-endian `u64` at `offset`.
+    pub fn memcmp_u64(self, offset: usize, value: u64) -> Self {
+        self.memcmp_bytes(offset, &value.to_le_bytes())
+    }
+
+    /// Adds an already encoded filter as is.
+    pub fn filter(mut self, filter: ProgramAccountFilter) -> Self {
+        self.entries.push(Entry::Filter(filter));
+        self
+    }
+
+    pub fn build(self) -> Result<Vec<ProgramAccountFilter>, FilterError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let encoding = self.encoding;
+        let filters: Vec<_> = self
+            .entries
+            .into_iter()
+            .map(|entry| match entry {
+                Entry::Memcmp { offset, bytes } => ProgramAccountFilter::Memcmp(match encoding {
+                    MemcmpEncoding::Base58 => MemcmpFilter::base58(offset, &bytes),
+                    MemcmpEncoding::Base64 => MemcmpFilter::base64(offset, &bytes),
+                }),
+                Entry::Filter(filter) => filter,
+            })
+            .collect();
+
+        validate_filters(&filters)?;
+        Ok(filters)
+    }
+
+    fn fail(mut self, error: FilterError) -> Self {
+        self.error.get_or_insert(error);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::prelude::{Engine, BASE64_STANDARD};
+    use serde_json::json;
+
+    const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    #[test]
+    fn builds_token_presets() {
+        let mint = BASE64_STANDARD.encode(bs58::decode(MINT).into_vec().unwrap());
+        let filters = Filters::token_accounts_for_mint(MINT).token_account_state().build().unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&filters).unwrap(),
+            json!([
+                { "dataSize": 165 },
+                { "memcmp": { "offset": 0, "bytes": mint, "encoding": "base64" } },
+                "tokenAccountState",
+            ])
+        );
+    }
+
+    #[test]
+    fn encodes_memcmp_bytes_as_requested() {
+        let filters = Filters::new()
+            .with_encoding(MemcmpEncoding::Base58)
+            .memcmp_u64(40, 7)
+            .build()
+            .unwrap();
+
+        let ProgramAccountFilter::Memcmp(memcmp) = &filters[0] else {
+            panic!("expected a memcmp filter");
+        };
+        assert_eq!(memcmp.encoding, Some(MemcmpEncoding::Base58));
+        assert_eq!(memcmp.decoded_bytes().unwrap(), 7u64.to_le_bytes());
+    }
+
+    #[test]
+    fn enforces_the_filter_count() {
+        let filters = vec![ProgramAccountFilter::DataSize(1); MAX_FILTERS];
+        assert!(validate_filters(&filters).is_ok());
+
+        let filters = vec![ProgramAccountFilter::DataSize(1); MAX_FILTERS + 1];
+        assert_eq!(
+            validate_filters(&filters),
+            Err(FilterError::TooManyFilters { count: MAX_FILTERS + 1 })
+        );
+    }
+
+    #[test]
+    fn enforces_the_memcmp_size() {
+        assert!(Filters::new().memcmp_bytes(0, &[1; MAX_MEMCMP_BYTES]).build().is_ok());
+        assert_eq!(
+            Filters::new().memcmp_bytes(8, &[1; MAX_MEMCMP_BYTES + 1]).build(),
+            Err(FilterError::MemcmpTooLarge {
+                offset: 8,
+                len: MAX_MEMCMP_BYTES + 1
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_bytes_and_pubkeys() {
+        let memcmp = MemcmpFilter {
+            offset: 4,
+            bytes: "not base64!".to_string(),
+            encoding: Some(MemcmpEncoding::Base64),
+        };
+        assert_eq!(
+            validate_filters(&[ProgramAccountFilter::Memcmp(memcmp)]),
+            Err(FilterError::InvalidBytes { offset: 4 })
+        );
+
+        // The first error is kept even when later entries are valid.
+        assert_eq!(
+            Filters::new().memcmp_pubkey(0, "short").data_size(165).build(),
+            Err(FilterError::InvalidPubkey("short".to_string()))
+        );
+    }
+}
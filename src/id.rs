@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// JSON-RPC request id, either a number or a string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RequestId {
+    Number(u64),
+    String(String),
+}
+
+impl From<u64> for RequestId {
+    fn from(id: u64) -> Self {
+        RequestId::Number(id)
+    }
+}
+
+impl From<String> for RequestId {
+    fn from(id: String) -> Self {
+        RequestId::String(id)
+    }
+}
+
+impl From<&str> for RequestId {
+    fn from(id: &str) -> Self {
+        RequestId::String(id.to_string())
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RequestId::Number(id) => write!(f, "{}", id),
+            RequestId::String(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+/// Produces the id of each request sent by a [`JsonRpcClient`](crate::JsonRpcClient).
+///
+/// Any `Fn() -> RequestId` closure is a generator, e.g. one returning UUIDs to
+/// correlate requests with provider logs.
+pub trait IdGenerator: Send + Sync {
+    fn next_id(&self) -> RequestId;
+}
+
+impl<F> IdGenerator for F
+where
+    F: Fn() -> RequestId + Send + Sync,
+{
+    fn next_id(&self) -> RequestId {
+        self()
+    }
+}
+
+impl fmt::Debug for dyn IdGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("IdGenerator")
+    }
+}
+
+/// Numeric ids counting up from 1. This is the default generator.
+#[derive(Debug)]
+pub struct SequentialIds(AtomicU64);
+
+impl Default for SequentialIds {
+    fn default() -> Self {
+        Self(AtomicU64::new(1))
+    }
+}
+
+impl IdGenerator for SequentialIds {
+    fn next_id(&self) -> RequestId {
+        RequestId::Number(self.0.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sequential_ids_count_up_from_one() {
+        let ids = SequentialIds::default();
+        assert_eq!(ids.next_id(), RequestId::Number(1));
+        assert_eq!(ids.next_id(), RequestId::Number(2));
+    }
+
+    #[test]
+    fn ids_serialize_as_bare_numbers_and_strings() {
+        assert_eq!(serde_json::to_string(&RequestId::from(7)).unwrap(), "7");
+        assert_eq!(serde_json::to_string(&RequestId::from("a-1")).unwrap(), r#""a-1""#);
+        assert_eq!(serde_json::from_str::<RequestId>("7").unwrap(), RequestId::Number(7));
+        assert_eq!(serde_json::from_str::<RequestId>(r#""7""#).unwrap(), RequestId::from("7"));
+    }
+}
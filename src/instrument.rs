@@ -0,0 +1,117 @@
+use crate::error::{JsonRpcError, Result};
+use crate::id::RequestId;
+use crate::types::RpcResponse;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tracing::field::Empty;
+use tracing::{Instrument, Span};
+
+pub(crate) fn span(method: &str, url: &str) -> Span {
+    // Only the host is recorded; provider URLs often carry an API key in the
+    // path or query string.
+    let endpoint = url::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_owned))
+        .unwrap_or_default();
+
+    tracing::debug_span!(
+        "rpc_call",
+        method,
+        endpoint,
+        request_id = Empty,
+        response_size = Empty,
+        context_slot = Empty,
+    )
+}
+
+/// Runs a whole call, from cache lookup to deadline, inside its `rpc_call`
+/// span and records how it ended.
+pub(crate) async fn instrumented<T, F>(method: &str, url: &str, call: F) -> Result<T>
+where
+    T: RpcResponse,
+    F: Future<Output = Result<T>>,
+{
+    let tracked = Call::start(method, url);
+    let result = call.instrument(tracked.span.clone()).await;
+
+    if let Some(slot) = result.as_ref().ok().and_then(RpcResponse::slot) {
+        tracked.span.record("context_slot", slot);
+    }
+    tracked.finish(result.as_ref().err());
+    result
+}
+
+/// The span and start time of a call, for calls that outlive a single
+/// future such as streams.
+#[derive(Debug)]
+pub(crate) struct Call {
+    span: Span,
+    method: String,
+    started: Instant,
+    response_size: usize,
+}
+
+impl Call {
+    pub(crate) fn start(method: &str, url: &str) -> Self {
+        Self {
+            span: span(method, url),
+            method: method.to_string(),
+            started: Instant::now(),
+            response_size: 0,
+        }
+    }
+
+    pub(crate) fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Counts a chunk of a response that is read incrementally.
+    pub(crate) fn received(&mut self, bytes: usize) {
+        self.response_size += bytes;
+        self.span.record("response_size", self.response_size);
+        metrics::counter!("solana_rpc_response_bytes_total", "method" => self.method.clone())
+            .increment(bytes as u64);
+    }
+
+    pub(crate) fn finish(&self, error: Option<&JsonRpcError>) {
+        self.span
+            .in_scope(|| finished(&self.method, self.started.elapsed(), error));
+    }
+}
+
+pub(crate) fn sent(method: &str, id: &RequestId, bytes: usize) {
+    Span::current().record("request_id", tracing::field::display(id));
+    metrics::counter!("solana_rpc_requests_total", "method" => method.to_string()).increment(1);
+    metrics::counter!("solana_rpc_request_bytes_total", "method" => method.to_string())
+        .increment(bytes as u64);
+}
+
+pub(crate) fn received(method: &str, bytes: usize) {
+    Span::current().record("response_size", bytes);
+    metrics::counter!("solana_rpc_response_bytes_total", "method" => method.to_string())
+        .increment(bytes as u64);
+}
+
+fn finished(method: &str, elapsed: Duration, error: Option<&JsonRpcError>) {
+    metrics::histogram!("solana_rpc_request_duration_seconds", "method" => method.to_string())
+        .record(elapsed.as_secs_f64());
+
+    if let Some(err) = error {
+        let code = rpc_code(err).map(|code| code.to_string()).unwrap_or_default();
+        metrics::counter!(
+            "solana_rpc_errors_total",
+            "method" => method.to_string(),
+            "kind" => err.kind(),
+            "code" => code,
+        )
+        .increment(1);
+        tracing::debug!(error = %err, "rpc call failed");
+    }
+}
+
+fn rpc_code(err: &JsonRpcError) -> Option<i64> {
+    match err.root() {
+        JsonRpcError::Rpc { code, .. } => Some(*code),
+        _ => None,
+    }
+}
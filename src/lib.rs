@@ -1,7 +1,34 @@
+pub mod anchor;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod cache;
+pub mod cassette;
 pub mod client;
+mod coalesce;
+pub mod deserialize;
 pub mod error;
+pub mod filter;
+pub mod id;
+#[cfg(feature = "instrumentation")]
+mod instrument;
+pub mod limit;
+pub mod loader;
 pub mod methods;
+mod session;
+mod stream;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 pub mod types;
 
-pub use client::JsonRpcClient;
+pub use cache::{CachePolicy, CacheStorage, LruStorage, ResponseCache};
+pub use cassette::Cassette;
+pub use client::{CallOptions, JsonRpcClient};
+pub use coalesce::CoalescingStats;
+pub use deserialize::{AccountDeserialize, AccountDeserializeError};
 pub use error::{JsonRpcError, Result};
+pub use filter::{FilterError, Filters};
+pub use id::{IdGenerator, RequestId, SequentialIds};
+pub use limit::{RateLimit, RateLimiter};
+pub use loader::AccountLoader;
+pub use session::Session;
+pub use tokio_util::sync::CancellationToken;
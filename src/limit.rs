@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+use std::num::{NonZeroU32, NonZeroUsize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+/// Token bucket parameters: `rate` tokens are added per second, up to `burst`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    rate: f64,
+    burst: f64,
+}
+
+impl RateLimit {
+    /// `rate` requests per second, with a burst of the same size.
+    pub fn per_second(rate: NonZeroU32) -> Self {
+        Self {
+            rate: rate.get() as f64,
+            burst: rate.get() as f64,
+        }
+    }
+
+    pub fn with_burst(mut self, burst: NonZeroU32) -> Self {
+        self.burst = burst.get() as f64;
+        self
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn burst(&self) -> f64 {
+        self.burst
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            state: Mutex::new((limit.burst, Instant::now())),
+        }
+    }
+
+    // Tokens are taken immediately and may go negative; the caller then sleeps
+    // until the debt would have been refilled. This keeps waiters in the order
+    // they arrived without a queue, and charges a cost above the burst in
+    // full rather than capping it.
+    async fn acquire(&self, tokens: f64) {
+        let wait = {
+            let mut state = self.state.lock().unwrap();
+            let (available, last) = &mut *state;
+            let now = Instant::now();
+            *available = (*available + now.duration_since(*last).as_secs_f64() * self.limit.rate)
+                .min(self.limit.burst);
+            *last = now;
+            *available -= tokens;
+            if *available < 0.0 {
+                Duration::from_secs_f64(-*available / self.limit.rate)
+            } else {
+                Duration::ZERO
+            }
+        };
+        if !wait.is_zero() {
+            let refund = Refund { bucket: self, tokens };
+            tokio::time::sleep(wait).await;
+            std::mem::forget(refund);
+        }
+    }
+}
+
+// Gives the tokens of a caller that stops waiting back to the bucket, so a
+// call cancelled or timed out before it was sent doesn't hold back the
+// callers after it.
+struct Refund<'a> {
+    bucket: &'a TokenBucket,
+    tokens: f64,
+}
+
+impl Drop for Refund<'_> {
+    fn drop(&mut self) {
+        let mut state = self.bucket.state.lock().unwrap();
+        state.0 = (state.0 + self.tokens).min(self.bucket.limit.burst);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Limits {
+    bucket: Option<TokenBucket>,
+    in_flight: Option<Arc<Semaphore>>,
+}
+
+impl Limits {
+    async fn acquire(&self, tokens: f64) -> Option<OwnedSemaphorePermit> {
+        // Hold the concurrency slot before taking tokens so requests waiting
+        // on the semaphore don't burn through the rate budget.
+        let permit = match &self.in_flight {
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        if let Some(bucket) = &self.bucket {
+            bucket.acquire(tokens).await;
+        }
+        permit
+    }
+}
+
+/// Client-side rate and concurrency limits for one endpoint.
+///
+/// Endpoint-wide limits are counted in credits, where each method costs `1`
+/// unless a different cost is set with [`RateLimiter::with_cost`]. Per-method
+/// limits are counted in requests. Callers over a limit wait instead of
+/// failing; a call cancelled or timed out while waiting gives its credits
+/// back.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    endpoint: Limits,
+    methods: HashMap<String, Limits>,
+    costs: HashMap<String, u32>,
+}
+
+/// Slots held by a request until it completes.
+#[derive(Debug)]
+pub(crate) struct RatePermit {
+    _endpoint: Option<OwnedSemaphorePermit>,
+    _method: Option<OwnedSemaphorePermit>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rate(mut self, limit: RateLimit) -> Self {
+        self.endpoint.bucket = Some(TokenBucket::new(limit));
+        self
+    }
+
+    pub fn with_max_in_flight(mut self, max: NonZeroUsize) -> Self {
+        self.endpoint.in_flight = Some(Arc::new(Semaphore::new(max.get())));
+        self
+    }
+
+    pub fn with_method_rate(mut self, method: impl Into<String>, limit: RateLimit) -> Self {
+        self.methods.entry(method.into()).or_default().bucket = Some(TokenBucket::new(limit));
+        self
+    }
+
+    pub fn with_method_max_in_flight(mut self, method: impl Into<String>, max: NonZeroUsize) -> Self {
+        self.methods.entry(method.into()).or_default().in_flight = Some(Arc::new(Semaphore::new(max.get())));
+        self
+    }
+
+    /// Sets how many endpoint credits a call to `method` consumes. A cost
+    /// above the endpoint's burst is still charged in full: the call waits
+    /// until the excess has been refilled.
+    pub fn with_cost(mut self, method: impl Into<String>, credits: u32) -> Self {
+        self.costs.insert(method.into(), credits);
+        self
+    }
+
+    pub fn cost(&self, method: &str) -> u32 {
+        self.costs.get(method).copied().unwrap_or(1)
+    }
+
+    pub(crate) async fn acquire(&self, method: &str) -> RatePermit {
+        let method_permit = match self.methods.get(method) {
+            Some(limits) => limits.acquire(1.0).await,
+            None => None,
+        };
+        let endpoint_permit = self.endpoint.acquire(self.cost(method) as f64).await;
+
+        RatePermit {
+            _endpoint: endpoint_permit,
+            _method: method_permit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nonzero(n: u32) -> NonZeroU32 {
+        NonZeroU32::new(n).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn burst_is_served_immediately_then_rate_applies() {
+        let limiter = RateLimiter::new().with_rate(RateLimit::per_second(nonzero(10)).with_burst(nonzero(2)));
+
+        let started = Instant::now();
+        limiter.acquire("getSlot").await;
+        limiter.acquire("getSlot").await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+
+        limiter.acquire("getSlot").await;
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cost_above_burst_is_charged_in_full() {
+        let limiter = RateLimiter::new()
+            .with_rate(RateLimit::per_second(nonzero(100)).with_burst(nonzero(5)))
+            .with_cost("getProgramAccounts", 15);
+
+        let started = Instant::now();
+        limiter.acquire("getProgramAccounts").await;
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn method_limits_do_not_apply_to_other_methods() {
+        let limiter = RateLimiter::new().with_method_rate("getProgramAccounts", RateLimit::per_second(nonzero(1)));
+
+        let started = Instant::now();
+        limiter.acquire("getProgramAccounts").await;
+        for _ in 0..10 {
+            limiter.acquire("getSlot").await;
+        }
+        assert_eq!(started.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancelled_waiters_refund_their_tokens() {
+        let limiter = RateLimiter::new().with_rate(RateLimit::per_second(nonzero(10)).with_burst(nonzero(1)));
+        limiter.acquire("getSlot").await;
+
+        let waiting = tokio::time::timeout(Duration::from_millis(10), limiter.acquire("getSlot")).await;
+        assert!(waiting.is_err());
+
+        // One token has been refilled since; without the refund it would
+        // only have paid off the cancelled caller's debt.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let started = Instant::now();
+        limiter.acquire("getSlot").await;
+        assert_eq!(started.elapsed(), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn max_in_flight_holds_callers_until_a_permit_drops() {
+        let limiter = RateLimiter::new().with_max_in_flight(NonZeroUsize::new(1).unwrap());
+
+        let permit = limiter.acquire("getSlot").await;
+        let waiting = tokio::time::timeout(Duration::from_millis(20), limiter.acquire("getSlot")).await;
+        assert!(waiting.is_err());
+
+        drop(permit);
+        let acquired = tokio::time::timeout(Duration::from_millis(20), limiter.acquire("getSlot")).await;
+        assert!(acquired.is_ok());
+    }
+
+    #[test]
+    fn cost_defaults_to_one() {
+        let limiter = RateLimiter::new().with_cost("getProgramAccounts", 10);
+        assert_eq!(limiter.cost("getProgramAccounts"), 10);
+        assert_eq!(limiter.cost("getSlot"), 1);
+    }
+}
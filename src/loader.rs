@@ -0,0 +1,179 @@
+use crate::client::JsonRpcClient;
+use crate::error::{JsonRpcError, Result};
+use crate::methods::account::{AccountInfoConfig, RpcGetMultipleAccountsRequest};
+use crate::types::AccountInfo;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Maximum number of pubkeys the server accepts in one `getMultipleAccounts`.
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Maximum number of `getMultipleAccounts` calls
+/// [`JsonRpcClient::get_multiple_accounts`] has in flight at once.
+pub const MAX_CONCURRENT_CHUNKS: usize = 4;
+
+type Waiter = oneshot::Sender<std::result::Result<Option<AccountInfo>, Arc<JsonRpcError>>>;
+
+impl JsonRpcClient {
+    /// Fetches any number of accounts, splitting the keys into
+    /// `getMultipleAccounts` calls of at most [`MAX_MULTIPLE_ACCOUNTS`], up
+    /// to [`MAX_CONCURRENT_CHUNKS`] of them at a time. Any rate limiter set
+    /// on the client applies to each call as usual. Results are returned in
+    /// the order of `pubkeys`.
+    pub async fn get_multiple_accounts(
+        &self,
+        pubkeys: &[String],
+        config: Option<AccountInfoConfig>,
+    ) -> Result<Vec<Option<AccountInfo>>> {
+        let chunks: Vec<_> = pubkeys
+            .chunks(MAX_MULTIPLE_ACCOUNTS)
+            .map(|chunk| {
+                let expected = chunk.len();
+                let request = RpcGetMultipleAccountsRequest {
+                    pubkeys: chunk.to_vec(),
+                    config: config.clone(),
+                };
+                async move {
+                    let accounts = self.call(request).await?.value;
+                    if accounts.len() != expected {
+                        return Err(JsonRpcError::InvalidResponse(format!(
+                            "expected {} accounts, got {}",
+                            expected,
+                            accounts.len()
+                        )));
+                    }
+                    Ok(accounts)
+                }
+            })
+            .collect();
+
+        let chunks: Vec<_> = stream::iter(chunks)
+            .buffered(MAX_CONCURRENT_CHUNKS)
+            .try_collect()
+            .await?;
+        Ok(chunks.into_iter().flatten().collect())
+    }
+}
+
+/// Batches individual account lookups into `getMultipleAccounts` calls.
+///
+/// Every [`AccountLoader::load`] made within `window` of the first pending one
+/// is merged into a single batch, which is flushed early once it reaches
+/// [`MAX_MULTIPLE_ACCOUNTS`] distinct keys.
+#[derive(Clone)]
+pub struct AccountLoader {
+    client: JsonRpcClient,
+    config: Option<AccountInfoConfig>,
+    window: Duration,
+    pending: Arc<Mutex<Batch>>,
+}
+
+#[derive(Default)]
+struct Batch {
+    generation: u64,
+    waiters: HashMap<String, Vec<Waiter>>,
+}
+
+impl AccountLoader {
+    pub fn new(client: JsonRpcClient) -> Self {
+        Self {
+            client,
+            config: None,
+            window: Duration::from_millis(5),
+            pending: Arc::default(),
+        }
+    }
+
+    pub fn with_config(mut self, config: AccountInfoConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub fn with_window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub async fn load(&self, pubkey: impl Into<String>) -> Result<Option<AccountInfo>> {
+        let (tx, rx) = oneshot::channel();
+
+        let full = {
+            let mut batch = self.pending.lock().unwrap();
+            let first = batch.waiters.is_empty();
+            batch.waiters.entry(pubkey.into()).or_default().push(tx);
+
+            if batch.waiters.len() >= MAX_MULTIPLE_ACCOUNTS {
+                Some(self.take(&mut batch))
+            } else {
+                if first {
+                    self.schedule(batch.generation);
+                }
+                None
+            }
+        };
+        if let Some(waiters) = full {
+            tokio::spawn(self.clone().dispatch(waiters));
+        }
+
+        match rx.await {
+            Ok(result) => result.map_err(JsonRpcError::Shared),
+            Err(_) => Err(JsonRpcError::InvalidResponse(
+                "account loader batch was dropped".to_string(),
+            )),
+        }
+    }
+
+    fn schedule(&self, generation: u64) {
+        let loader = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(loader.window).await;
+            let waiters = {
+                let mut batch = loader.pending.lock().unwrap();
+                if batch.generation != generation {
+                    // Already flushed because it filled up.
+                    return;
+                }
+                loader.take(&mut batch)
+            };
+            loader.dispatch(waiters).await;
+        });
+    }
+
+    fn take(&self, batch: &mut Batch) -> HashMap<String, Vec<Waiter>> {
+        batch.generation += 1;
+        std::mem::take(&mut batch.waiters)
+    }
+
+    async fn dispatch(self, waiters: HashMap<String, Vec<Waiter>>) {
+        let (pubkeys, waiters): (Vec<_>, Vec<_>) = waiters.into_iter().unzip();
+
+        match self.client.get_multiple_accounts(&pubkeys, self.config.clone()).await {
+            Ok(accounts) => {
+                for (account, waiters) in accounts.into_iter().zip(waiters) {
+                    for waiter in waiters {
+                        let _ = waiter.send(Ok(account.clone()));
+                    }
+                }
+            }
+            Err(err) => {
+                let err = Arc::new(err);
+                for waiter in waiters.into_iter().flatten() {
+                    let _ = waiter.send(Err(err.clone()));
+                }
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for AccountLoader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccountLoader")
+            .field("client", &self.client)
+            .field("config", &self.config)
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
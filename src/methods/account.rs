@@ -1,5 +1,5 @@
 use crate::client::RpcRequest;
-use crate::types::{AccountInfo, Commitment, Encoding, RpcContext};
+use crate::types::{AccountInfo, Commitment, Encoding, Lamports, Response};
 use serde::{Deserialize, Serialize};
 
 // getAccountInfo
@@ -24,7 +24,8 @@ impl Serialize for RpcGetAccountInfoRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct AccountInfoConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<Encoding>,
@@ -42,12 +43,7 @@ pub struct DataSliceConfig {
     pub length: usize,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetAccountInfoResponse {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub value: Option<AccountInfo>,
-    pub context: RpcContext,
-}
+pub type RpcGetAccountInfoResponse = Response<Option<AccountInfo>>;
 
 impl RpcRequest for RpcGetAccountInfoRequest {
     fn method_name() -> &'static str {
@@ -55,6 +51,14 @@ impl RpcRequest for RpcGetAccountInfoRequest {
     }
     
     type Response = RpcGetAccountInfoResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.get_or_insert_with(Default::default).min_context_slot)
+    }
 }
 
 // getBalance
@@ -79,7 +83,8 @@ impl Serialize for RpcGetBalanceRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BalanceConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commitment: Option<Commitment>,
@@ -87,11 +92,7 @@ pub struct BalanceConfig {
     pub min_context_slot: Option<u64>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetBalanceResponse {
-    pub value: u64,
-    pub context: RpcContext,
-}
+pub type RpcGetBalanceResponse = Response<Lamports>;
 
 impl RpcRequest for RpcGetBalanceRequest {
     fn method_name() -> &'static str {
@@ -99,6 +100,14 @@ impl RpcRequest for RpcGetBalanceRequest {
     }
     
     type Response = RpcGetBalanceResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.get_or_insert_with(Default::default).min_context_slot)
+    }
 }
 
 // getMultipleAccounts
@@ -123,11 +132,7 @@ impl Serialize for RpcGetMultipleAccountsRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetMultipleAccountsResponse {
-    pub value: Vec<Option<AccountInfo>>,
-    pub context: RpcContext,
-}
+pub type RpcGetMultipleAccountsResponse = Response<Vec<Option<AccountInfo>>>;
 
 impl RpcRequest for RpcGetMultipleAccountsRequest {
     fn method_name() -> &'static str {
@@ -135,6 +140,14 @@ impl RpcRequest for RpcGetMultipleAccountsRequest {
     }
     
     type Response = RpcGetMultipleAccountsResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.get_or_insert_with(Default::default).min_context_slot)
+    }
 }
 
 // getLargestAccounts
@@ -166,7 +179,7 @@ pub struct LargestAccountsConfig {
 }
 
 #[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "camelCase")]
 pub enum LargestAccountsFilter {
     Circulating,
     NonCirculating,
@@ -175,14 +188,10 @@ pub enum LargestAccountsFilter {
 #[derive(Debug, Clone, Deserialize)]
 pub struct AccountBalance {
     pub address: String,
-    pub lamports: u64,
+    pub lamports: Lamports,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetLargestAccountsResponse {
-    pub value: Vec<AccountBalance>,
-    pub context: RpcContext,
-}
+pub type RpcGetLargestAccountsResponse = Response<Vec<AccountBalance>>;
 
 impl RpcRequest for RpcGetLargestAccountsRequest {
     fn method_name() -> &'static str {
@@ -190,6 +199,10 @@ impl RpcRequest for RpcGetLargestAccountsRequest {
     }
     
     type Response = RpcGetLargestAccountsResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
 }
 
 // getMinimumBalanceForRentExemption
@@ -214,10 +227,7 @@ impl Serialize for RpcGetMinimumBalanceForRentExemptionRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetMinimumBalanceForRentExemptionResponse {
-    pub value: u64,
-}
+pub type RpcGetMinimumBalanceForRentExemptionResponse = Response<Lamports>;
 
 impl RpcRequest for RpcGetMinimumBalanceForRentExemptionRequest {
     fn method_name() -> &'static str {
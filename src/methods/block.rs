@@ -1,5 +1,5 @@
 use crate::client::RpcRequest;
-use crate::types::{Block, BlockEncoding, Commitment, RpcContext};
+use crate::types::{Block, BlockEncoding, Commitment, Hash, Response};
 use serde::{Deserialize, Serialize};
 
 // getBlock
@@ -10,6 +10,7 @@ pub struct RpcGetBlockRequest {
 }
 
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BlockConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<BlockEncoding>,
@@ -46,16 +47,7 @@ impl Serialize for RpcGetBlockRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(untagged)]
-pub enum RpcGetBlockResponse {
-    Simple(Option<Block>),
-    WithContext {
-        #[serde(skip_serializing_if = "Option::is_none")]
-        value: Option<Block>,
-        context: RpcContext,
-    },
-}
+pub type RpcGetBlockResponse = Response<Option<Block>>;
 
 impl RpcRequest for RpcGetBlockRequest {
     fn method_name() -> &'static str {
@@ -82,6 +74,7 @@ impl Serialize for RpcGetBlockHeightRequest {
         let mut seq = serializer.serialize_seq(Some(len))?;
         if self.commitment.is_some() || self.min_context_slot.is_some() {
             #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
             struct Config {
                 #[serde(skip_serializing_if = "Option::is_none")]
                 commitment: Option<Commitment>,
@@ -97,15 +90,7 @@ impl Serialize for RpcGetBlockHeightRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(untagged)]
-pub enum RpcGetBlockHeightResponse {
-    Simple(u64),
-    WithContext {
-        value: u64,
-        context: RpcContext,
-    },
-}
+pub type RpcGetBlockHeightResponse = Response<u64>;
 
 impl RpcRequest for RpcGetBlockHeightRequest {
     fn method_name() -> &'static str {
@@ -113,6 +98,10 @@ impl RpcRequest for RpcGetBlockHeightRequest {
     }
     
     type Response = RpcGetBlockHeightResponse;
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.min_context_slot)
+    }
 }
 
 // getSlot
@@ -132,6 +121,7 @@ impl Serialize for RpcGetSlotRequest {
         let mut seq = serializer.serialize_seq(Some(len))?;
         if self.commitment.is_some() || self.min_context_slot.is_some() {
             #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
             struct Config {
                 #[serde(skip_serializing_if = "Option::is_none")]
                 commitment: Option<Commitment>,
@@ -147,15 +137,7 @@ impl Serialize for RpcGetSlotRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(untagged)]
-pub enum RpcGetSlotResponse {
-    Simple(u64),
-    WithContext {
-        value: u64,
-        context: RpcContext,
-    },
-}
+pub type RpcGetSlotResponse = Response<u64>;
 
 impl RpcRequest for RpcGetSlotRequest {
     fn method_name() -> &'static str {
@@ -163,6 +145,10 @@ impl RpcRequest for RpcGetSlotRequest {
     }
     
     type Response = RpcGetSlotResponse;
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.min_context_slot)
+    }
 }
 
 // getSlotLeader
@@ -190,11 +176,7 @@ impl Serialize for RpcGetSlotLeaderRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetSlotLeaderResponse {
-    pub value: String,
-    pub context: RpcContext,
-}
+pub type RpcGetSlotLeaderResponse = Response<String>;
 
 impl RpcRequest for RpcGetSlotLeaderRequest {
     fn method_name() -> &'static str {
@@ -221,6 +203,7 @@ impl Serialize for RpcGetLatestBlockhashRequest {
         let mut seq = serializer.serialize_seq(Some(len))?;
         if self.commitment.is_some() || self.min_context_slot.is_some() {
             #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
             struct Config {
                 #[serde(skip_serializing_if = "Option::is_none")]
                 commitment: Option<Commitment>,
@@ -237,16 +220,13 @@ impl Serialize for RpcGetLatestBlockhashRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BlockhashValue {
-    pub blockhash: String,
+    pub blockhash: Hash,
     pub last_valid_block_height: u64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetLatestBlockhashResponse {
-    pub value: BlockhashValue,
-    pub context: RpcContext,
-}
+pub type RpcGetLatestBlockhashResponse = Response<BlockhashValue>;
 
 impl RpcRequest for RpcGetLatestBlockhashRequest {
     fn method_name() -> &'static str {
@@ -254,16 +234,25 @@ impl RpcRequest for RpcGetLatestBlockhashRequest {
     }
     
     type Response = RpcGetLatestBlockhashResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.min_context_slot)
+    }
 }
 
 // isBlockhashValid
 #[derive(Debug, Clone)]
 pub struct RpcIsBlockhashValidRequest {
-    pub blockhash: String,
+    pub blockhash: Hash,
     pub config: Option<BlockhashValidConfig>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BlockhashValidConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commitment: Option<Commitment>,
@@ -286,11 +275,7 @@ impl Serialize for RpcIsBlockhashValidRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcIsBlockhashValidResponse {
-    pub value: bool,
-    pub context: RpcContext,
-}
+pub type RpcIsBlockhashValidResponse = Response<bool>;
 
 impl RpcRequest for RpcIsBlockhashValidRequest {
     fn method_name() -> &'static str {
@@ -298,6 +283,14 @@ impl RpcRequest for RpcIsBlockhashValidRequest {
     }
     
     type Response = RpcIsBlockhashValidResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.get_or_insert_with(Default::default).min_context_slot)
+    }
 }
 
 // getBlocks
@@ -327,11 +320,7 @@ impl Serialize for RpcGetBlocksRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetBlocksResponse {
-    pub value: Vec<u64>,
-    pub context: RpcContext,
-}
+pub type RpcGetBlocksResponse = Response<Vec<u64>>;
 
 impl RpcRequest for RpcGetBlocksRequest {
     fn method_name() -> &'static str {
@@ -356,10 +345,7 @@ impl Serialize for RpcGetFirstAvailableBlockRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetFirstAvailableBlockResponse {
-    pub value: u64,
-}
+pub type RpcGetFirstAvailableBlockResponse = Response<u64>;
 
 impl RpcRequest for RpcGetFirstAvailableBlockRequest {
     fn method_name() -> &'static str {
@@ -1,5 +1,5 @@
 use crate::client::RpcRequest;
-use crate::types::{Commitment, RpcContext};
+use crate::types::{Commitment, Hash, Lamports, Response};
 use serde::{Deserialize, Serialize};
 
 // getVersion
@@ -21,14 +21,11 @@ impl Serialize for RpcGetVersionRequest {
 pub struct VersionInfo {
     #[serde(rename = "solana-core")]
     pub solana_core: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "feature-set", skip_serializing_if = "Option::is_none")]
     pub feature_set: Option<u64>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetVersionResponse {
-    pub value: VersionInfo,
-}
+pub type RpcGetVersionResponse = Response<VersionInfo>;
 
 impl RpcRequest for RpcGetVersionRequest {
     fn method_name() -> &'static str {
@@ -53,12 +50,7 @@ impl Serialize for RpcGetHealthRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-#[serde(untagged)]
-pub enum RpcGetHealthResponse {
-    Ok(String),
-    Error { code: i64, message: String },
-}
+pub type RpcGetHealthResponse = Response<String>;
 
 impl RpcRequest for RpcGetHealthRequest {
     fn method_name() -> &'static str {
@@ -84,6 +76,7 @@ impl Serialize for RpcGetClusterNodesRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ClusterNode {
     pub pubkey: String,
     pub gossip: Option<String>,
@@ -94,10 +87,7 @@ pub struct ClusterNode {
     pub shred_version: Option<u16>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetClusterNodesResponse {
-    pub value: Vec<ClusterNode>,
-}
+pub type RpcGetClusterNodesResponse = Response<Vec<ClusterNode>>;
 
 impl RpcRequest for RpcGetClusterNodesRequest {
     fn method_name() -> &'static str {
@@ -122,10 +112,7 @@ impl Serialize for RpcGetGenesisHashRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetGenesisHashResponse {
-    pub value: String,
-}
+pub type RpcGetGenesisHashResponse = Response<Hash>;
 
 impl RpcRequest for RpcGetGenesisHashRequest {
     fn method_name() -> &'static str {
@@ -150,10 +137,7 @@ impl Serialize for RpcGetIdentityRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetIdentityResponse {
-    pub value: IdentityInfo,
-}
+pub type RpcGetIdentityResponse = Response<IdentityInfo>;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct IdentityInfo {
@@ -185,10 +169,11 @@ impl Serialize for RpcGetSupplyRequest {
         let mut seq = serializer.serialize_seq(Some(len))?;
         if self.commitment.is_some() || self.exclude_non_circulating_supply_list.is_some() {
             #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
             struct Config {
                 #[serde(skip_serializing_if = "Option::is_none")]
                 commitment: Option<Commitment>,
-                #[serde(skip_serializing_if = "Option::is_none")]
+                #[serde(rename = "excludeNonCirculatingAccountsList", skip_serializing_if = "Option::is_none")]
                 exclude_non_circulating_supply_list: Option<bool>,
             }
             seq.serialize_element(&Config {
@@ -201,19 +186,16 @@ impl Serialize for RpcGetSupplyRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SupplyInfo {
-    pub total: u64,
-    pub circulating: u64,
-    pub non_circulating: u64,
+    pub total: Lamports,
+    pub circulating: Lamports,
+    pub non_circulating: Lamports,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub non_circulating_accounts: Option<Vec<String>>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetSupplyResponse {
-    pub value: SupplyInfo,
-    pub context: RpcContext,
-}
+pub type RpcGetSupplyResponse = Response<SupplyInfo>;
 
 impl RpcRequest for RpcGetSupplyRequest {
     fn method_name() -> &'static str {
@@ -221,6 +203,10 @@ impl RpcRequest for RpcGetSupplyRequest {
     }
     
     type Response = RpcGetSupplyResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
 }
 
 // getMaxRetransmitSlot
@@ -238,10 +224,7 @@ impl Serialize for RpcGetMaxRetransmitSlotRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetMaxRetransmitSlotResponse {
-    pub value: u64,
-}
+pub type RpcGetMaxRetransmitSlotResponse = Response<u64>;
 
 impl RpcRequest for RpcGetMaxRetransmitSlotRequest {
     fn method_name() -> &'static str {
@@ -266,10 +249,7 @@ impl Serialize for RpcGetMaxShredInsertSlotRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetMaxShredInsertSlotResponse {
-    pub value: u64,
-}
+pub type RpcGetMaxShredInsertSlotResponse = Response<u64>;
 
 impl RpcRequest for RpcGetMaxShredInsertSlotRequest {
     fn method_name() -> &'static str {
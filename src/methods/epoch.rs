@@ -1,5 +1,5 @@
 use crate::client::RpcRequest;
-use crate::types::{Commitment, RpcContext};
+use crate::types::{Commitment, Epoch, Response, Slot};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -20,6 +20,7 @@ impl Serialize for RpcGetEpochInfoRequest {
         let mut seq = serializer.serialize_seq(Some(len))?;
         if self.commitment.is_some() || self.min_context_slot.is_some() {
             #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
             struct Config {
                 #[serde(skip_serializing_if = "Option::is_none")]
                 commitment: Option<Commitment>,
@@ -36,20 +37,17 @@ impl Serialize for RpcGetEpochInfoRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EpochInfo {
-    pub absolute_slot: u64,
+    pub absolute_slot: Slot,
     pub block_height: u64,
-    pub epoch: u64,
+    pub epoch: Epoch,
     pub slot_index: u64,
     pub slots_in_epoch: u64,
     pub transaction_count: Option<u64>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetEpochInfoResponse {
-    pub value: EpochInfo,
-    pub context: RpcContext,
-}
+pub type RpcGetEpochInfoResponse = Response<EpochInfo>;
 
 impl RpcRequest for RpcGetEpochInfoRequest {
     fn method_name() -> &'static str {
@@ -57,6 +55,10 @@ impl RpcRequest for RpcGetEpochInfoRequest {
     }
     
     type Response = RpcGetEpochInfoResponse;
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.min_context_slot)
+    }
 }
 
 // getEpochSchedule
@@ -74,7 +76,8 @@ impl Serialize for RpcGetEpochScheduleRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EpochSchedule {
     pub slots_per_epoch: u64,
     pub leader_schedule_slot_offset: u64,
@@ -83,11 +86,83 @@ pub struct EpochSchedule {
     pub first_normal_slot: u64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetEpochScheduleResponse {
-    pub value: EpochSchedule,
+/// Length of the first epoch when warmup is enabled. Each warmup epoch is
+/// twice as long as the one before.
+pub const MINIMUM_SLOTS_PER_EPOCH: u64 = 32;
+
+// Arithmetic follows the runtime's `EpochSchedule`, including its handling
+// of a zero `slots_per_epoch`.
+impl EpochSchedule {
+    pub fn slots_in_epoch(&self, epoch: Epoch) -> u64 {
+        if epoch.0 < self.first_normal_epoch {
+            2u64.saturating_pow((epoch.0 + MINIMUM_SLOTS_PER_EPOCH.trailing_zeros() as u64) as u32)
+        } else {
+            self.slots_per_epoch
+        }
+    }
+
+    /// The epoch containing `slot` and the slot's index within it.
+    pub fn epoch_and_slot_index(&self, slot: Slot) -> (Epoch, u64) {
+        let slot = slot.0;
+        if slot < self.first_normal_slot {
+            let epoch = (slot + MINIMUM_SLOTS_PER_EPOCH + 1).next_power_of_two().trailing_zeros()
+                - MINIMUM_SLOTS_PER_EPOCH.trailing_zeros()
+                - 1;
+            let epoch_len = 2u64.pow(epoch + MINIMUM_SLOTS_PER_EPOCH.trailing_zeros());
+            (Epoch(epoch as u64), slot - (epoch_len - MINIMUM_SLOTS_PER_EPOCH))
+        } else {
+            let normal_slot_index = slot - self.first_normal_slot;
+            let normal_epoch_index = normal_slot_index.checked_div(self.slots_per_epoch).unwrap_or(0);
+            let slot_index = normal_slot_index.checked_rem(self.slots_per_epoch).unwrap_or(0);
+            (Epoch(self.first_normal_epoch + normal_epoch_index), slot_index)
+        }
+    }
+
+    pub fn epoch_for_slot(&self, slot: Slot) -> Epoch {
+        self.epoch_and_slot_index(slot).0
+    }
+
+    pub fn first_slot_in_epoch(&self, epoch: Epoch) -> Slot {
+        if epoch.0 <= self.first_normal_epoch {
+            Slot((2u64.saturating_pow(epoch.0 as u32) - 1).saturating_mul(MINIMUM_SLOTS_PER_EPOCH))
+        } else {
+            Slot(
+                (epoch.0 - self.first_normal_epoch)
+                    .saturating_mul(self.slots_per_epoch)
+                    .saturating_add(self.first_normal_slot),
+            )
+        }
+    }
+
+    pub fn last_slot_in_epoch(&self, epoch: Epoch) -> Slot {
+        Slot(
+            self.first_slot_in_epoch(epoch)
+                .0
+                .saturating_add(self.slots_in_epoch(epoch))
+                .saturating_sub(1),
+        )
+    }
+
+    /// The epoch whose leader schedule is known by `slot`: the leader
+    /// schedule for an epoch is fixed `leader_schedule_slot_offset` slots
+    /// before it starts.
+    pub fn leader_schedule_epoch(&self, slot: Slot) -> Epoch {
+        if slot.0 < self.first_normal_slot {
+            self.epoch_for_slot(slot).next()
+        } else {
+            let new_slots_since_first_normal_slot = slot.0 - self.first_normal_slot;
+            let new_first_normal_leader_schedule_slot =
+                new_slots_since_first_normal_slot.saturating_add(self.leader_schedule_slot_offset);
+            let new_epochs_since_first_normal_leader_schedule = new_first_normal_leader_schedule_slot
+                .checked_div(self.slots_per_epoch)
+                .unwrap_or(0);
+            Epoch(self.first_normal_epoch + new_epochs_since_first_normal_leader_schedule)
+        }
+    }
 }
 
+pub type RpcGetEpochScheduleResponse = Response<EpochSchedule>;
+
 impl RpcRequest for RpcGetEpochScheduleRequest {
     fn method_name() -> &'static str {
         "getEpochSchedule"
@@ -129,10 +204,7 @@ impl Serialize for RpcGetLeaderScheduleRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetLeaderScheduleResponse {
-    pub value: HashMap<String, Vec<u64>>,
-}
+pub type RpcGetLeaderScheduleResponse = Response<Option<HashMap<String, Vec<u64>>>>;
 
 impl RpcRequest for RpcGetLeaderScheduleRequest {
     fn method_name() -> &'static str {
@@ -142,3 +214,85 @@ impl RpcRequest for RpcGetLeaderScheduleRequest {
     type Response = RpcGetLeaderScheduleResponse;
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Mainnet's schedule, which has no warmup.
+    fn mainnet() -> EpochSchedule {
+        EpochSchedule {
+            slots_per_epoch: 432_000,
+            leader_schedule_slot_offset: 432_000,
+            warmup: false,
+            first_normal_epoch: 0,
+            first_normal_slot: 0,
+        }
+    }
+
+    // A test-validator schedule with warmup: epochs of 32, 64, ... 4096
+    // slots, then 8192 from epoch 8 at slot 8160.
+    fn warmup() -> EpochSchedule {
+        EpochSchedule {
+            slots_per_epoch: 8192,
+            leader_schedule_slot_offset: 8192,
+            warmup: true,
+            first_normal_epoch: 8,
+            first_normal_slot: 8160,
+        }
+    }
+
+    #[test]
+    fn splits_normal_slots() {
+        let schedule = mainnet();
+        assert_eq!(schedule.epoch_and_slot_index(Slot(250_000_000)), (Epoch(578), 304_000));
+        assert_eq!(schedule.first_slot_in_epoch(Epoch(578)), Slot(249_696_000));
+        assert_eq!(schedule.last_slot_in_epoch(Epoch(578)), Slot(250_127_999));
+        assert_eq!(schedule.leader_schedule_epoch(Slot(250_000_000)), Epoch(579));
+    }
+
+    #[test]
+    fn doubles_epochs_during_warmup() {
+        let schedule = warmup();
+        assert_eq!(schedule.slots_in_epoch(Epoch(0)), 32);
+        assert_eq!(schedule.slots_in_epoch(Epoch(7)), 4096);
+        assert_eq!(schedule.slots_in_epoch(Epoch(8)), 8192);
+
+        assert_eq!(schedule.epoch_and_slot_index(Slot(0)), (Epoch(0), 0));
+        assert_eq!(schedule.epoch_and_slot_index(Slot(31)), (Epoch(0), 31));
+        assert_eq!(schedule.epoch_and_slot_index(Slot(32)), (Epoch(1), 0));
+        assert_eq!(schedule.epoch_and_slot_index(Slot(8159)), (Epoch(7), 4095));
+        assert_eq!(schedule.epoch_and_slot_index(Slot(8160)), (Epoch(8), 0));
+
+        assert_eq!(schedule.first_slot_in_epoch(Epoch(8)), Slot(8160));
+        assert_eq!(schedule.leader_schedule_epoch(Slot(0)), Epoch(1));
+        assert_eq!(schedule.leader_schedule_epoch(Slot(8160)), Epoch(9));
+    }
+
+    #[test]
+    fn epochs_tile_the_slots() {
+        for schedule in [mainnet(), warmup()] {
+            let last = schedule.first_normal_slot + 3 * schedule.slots_per_epoch;
+            for slot in (0..last).step_by(7).chain([last]) {
+                let (epoch, index) = schedule.epoch_and_slot_index(Slot(slot));
+                assert!(index < schedule.slots_in_epoch(epoch), "slot {}", slot);
+                assert_eq!(schedule.first_slot_in_epoch(epoch).get() + index, slot, "slot {}", slot);
+            }
+            for epoch in 0..schedule.first_normal_epoch + 3 {
+                assert_eq!(
+                    schedule.last_slot_in_epoch(Epoch(epoch)).get() + 1,
+                    schedule.first_slot_in_epoch(Epoch(epoch + 1)).get()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn tolerates_zero_slots_per_epoch() {
+        let schedule = EpochSchedule {
+            slots_per_epoch: 0,
+            ..mainnet()
+        };
+        assert_eq!(schedule.epoch_and_slot_index(Slot(100)), (Epoch(0), 0));
+        assert_eq!(schedule.leader_schedule_epoch(Slot(100)), Epoch(0));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::client::RpcRequest;
-use crate::types::{Commitment, RpcContext};
+use crate::types::{Commitment, Lamports, Response};
 use serde::{Deserialize, Serialize};
 
 // getInflationGovernor
@@ -24,6 +24,7 @@ impl Serialize for RpcGetInflationGovernorRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InflationGovernor {
     pub initial: f64,
     pub terminal: f64,
@@ -32,11 +33,7 @@ pub struct InflationGovernor {
     pub foundation_term: f64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetInflationGovernorResponse {
-    pub value: InflationGovernor,
-    pub context: RpcContext,
-}
+pub type RpcGetInflationGovernorResponse = Response<InflationGovernor>;
 
 impl RpcRequest for RpcGetInflationGovernorRequest {
     fn method_name() -> &'static str {
@@ -69,10 +66,7 @@ pub struct InflationRate {
     pub epoch: u64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetInflationRateResponse {
-    pub value: InflationRate,
-}
+pub type RpcGetInflationRateResponse = Response<InflationRate>;
 
 impl RpcRequest for RpcGetInflationRateRequest {
     fn method_name() -> &'static str {
@@ -89,7 +83,8 @@ pub struct RpcGetInflationRewardRequest {
     pub config: Option<InflationRewardConfig>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InflationRewardConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub epoch: Option<u64>,
@@ -115,19 +110,16 @@ impl Serialize for RpcGetInflationRewardRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct InflationReward {
     pub epoch: u64,
     pub effective_slot: u64,
-    pub amount: u64,
-    pub post_balance: u64,
+    pub amount: Lamports,
+    pub post_balance: Lamports,
     pub commission: Option<u8>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetInflationRewardResponse {
-    pub value: Vec<Option<InflationReward>>,
-    pub context: RpcContext,
-}
+pub type RpcGetInflationRewardResponse = Response<Vec<Option<InflationReward>>>;
 
 impl RpcRequest for RpcGetInflationRewardRequest {
     fn method_name() -> &'static str {
@@ -135,6 +127,10 @@ impl RpcRequest for RpcGetInflationRewardRequest {
     }
     
     type Response = RpcGetInflationRewardResponse;
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.get_or_insert_with(Default::default).min_context_slot)
+    }
 }
 
 
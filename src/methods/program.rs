@@ -1,5 +1,6 @@
 use crate::client::RpcRequest;
-use crate::types::{AccountInfo, Commitment, Encoding, RpcContext};
+use crate::types::{AccountInfo, Commitment, Encoding, Response};
+use base64::prelude::{Engine, BASE64_STANDARD};
 use serde::{Deserialize, Serialize};
 
 // getProgramAccounts
@@ -9,7 +10,8 @@ pub struct RpcGetProgramAccountsRequest {
     pub config: Option<ProgramAccountsConfig>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ProgramAccountsConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commitment: Option<Commitment>,
@@ -25,23 +27,65 @@ pub struct ProgramAccountsConfig {
     pub with_context: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(untagged)]
+/// A `getProgramAccounts` filter. See [`crate::filter::Filters`] for a
+/// builder that checks the node's limits.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub enum ProgramAccountFilter {
-    Memcmp {
-        #[serde(rename = "memcmp")]
-        memcmp: MemcmpFilter,
-    },
-    DataSize {
-        #[serde(rename = "dataSize")]
-        data_size: usize,
-    },
+    Memcmp(MemcmpFilter),
+    DataSize(u64),
+    /// Matches initialized SPL Token and Token-2022 accounts.
+    TokenAccountState,
 }
 
-#[derive(Debug, Clone, Serialize)]
+impl ProgramAccountFilter {
+    /// Matches accounts whose data starts with `discriminator`.
+    pub fn discriminator(discriminator: &[u8]) -> Self {
+        ProgramAccountFilter::Memcmp(MemcmpFilter::base58(0, discriminator))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct MemcmpFilter {
     pub offset: usize,
     pub bytes: String,
+    /// Encoding of `bytes`; nodes assume base58 when it is absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<MemcmpEncoding>,
+}
+
+impl MemcmpFilter {
+    pub fn base58(offset: usize, bytes: &[u8]) -> Self {
+        Self {
+            offset,
+            bytes: bs58::encode(bytes).into_string(),
+            encoding: Some(MemcmpEncoding::Base58),
+        }
+    }
+
+    pub fn base64(offset: usize, bytes: &[u8]) -> Self {
+        Self {
+            offset,
+            bytes: BASE64_STANDARD.encode(bytes),
+            encoding: Some(MemcmpEncoding::Base64),
+        }
+    }
+
+    /// The bytes to compare, or `None` if they are not valid in their
+    /// encoding.
+    pub fn decoded_bytes(&self) -> Option<Vec<u8>> {
+        match self.encoding {
+            Some(MemcmpEncoding::Base64) => BASE64_STANDARD.decode(&self.bytes).ok(),
+            Some(MemcmpEncoding::Base58) | None => bs58::decode(&self.bytes).into_vec().ok(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemcmpEncoding {
+    Base58,
+    Base64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -71,11 +115,7 @@ pub struct ProgramAccount {
     pub pubkey: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetProgramAccountsResponse {
-    pub value: Vec<ProgramAccount>,
-    pub context: RpcContext,
-}
+pub type RpcGetProgramAccountsResponse = Response<Vec<ProgramAccount>>;
 
 impl RpcRequest for RpcGetProgramAccountsRequest {
     fn method_name() -> &'static str {
@@ -83,5 +123,13 @@ impl RpcRequest for RpcGetProgramAccountsRequest {
     }
     
     type Response = RpcGetProgramAccountsResponse;
+
+    fn has_context(&self) -> bool {
+        self.config.as_ref().and_then(|config| config.with_context).unwrap_or(false)
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.get_or_insert_with(Default::default).min_context_slot)
+    }
 }
 
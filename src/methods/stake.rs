@@ -1,5 +1,5 @@
 use crate::client::RpcRequest;
-use crate::types::RpcContext;
+use crate::types::{Lamports, Response};
 use serde::{Deserialize, Serialize};
 
 // getStakeActivation
@@ -9,7 +9,8 @@ pub struct RpcGetStakeActivationRequest {
     pub config: Option<StakeActivationConfig>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct StakeActivationConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub epoch: Option<u64>,
@@ -35,15 +36,11 @@ impl Serialize for RpcGetStakeActivationRequest {
 #[derive(Debug, Clone, Deserialize)]
 pub struct StakeActivation {
     pub state: String,
-    pub active: u64,
-    pub inactive: u64,
+    pub active: Lamports,
+    pub inactive: Lamports,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetStakeActivationResponse {
-    pub value: StakeActivation,
-    pub context: RpcContext,
-}
+pub type RpcGetStakeActivationResponse = Response<StakeActivation>;
 
 impl RpcRequest for RpcGetStakeActivationRequest {
     fn method_name() -> &'static str {
@@ -51,5 +48,9 @@ impl RpcRequest for RpcGetStakeActivationRequest {
     }
     
     type Response = RpcGetStakeActivationResponse;
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.get_or_insert_with(Default::default).min_context_slot)
+    }
 }
 
@@ -1,7 +1,10 @@
 use crate::client::RpcRequest;
-use crate::types::{Commitment, Encoding, RpcContext};
+use crate::types::parsed::UiTokenAccount;
+use crate::types::{AccountDataError, AccountInfo, AmountError, Commitment, Encoding, Response, TokenQuantity};
 use serde::{Deserialize, Serialize};
 
+pub use crate::types::parsed::TokenAmount;
+
 // getTokenAccountBalance
 #[derive(Debug, Clone)]
 pub struct RpcGetTokenAccountBalanceRequest {
@@ -21,6 +24,7 @@ impl Serialize for RpcGetTokenAccountBalanceRequest {
         seq.serialize_element(&self.account)?;
         if self.commitment.is_some() || self.min_context_slot.is_some() {
             #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
             struct Config {
                 #[serde(skip_serializing_if = "Option::is_none")]
                 commitment: Option<Commitment>,
@@ -36,19 +40,7 @@ impl Serialize for RpcGetTokenAccountBalanceRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct TokenAmount {
-    pub ui_amount: Option<f64>,
-    pub decimals: u8,
-    pub amount: String,
-    pub ui_amount_string: String,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetTokenAccountBalanceResponse {
-    pub value: TokenAmount,
-    pub context: RpcContext,
-}
+pub type RpcGetTokenAccountBalanceResponse = Response<TokenAmount>;
 
 impl RpcRequest for RpcGetTokenAccountBalanceRequest {
     fn method_name() -> &'static str {
@@ -56,6 +48,14 @@ impl RpcRequest for RpcGetTokenAccountBalanceRequest {
     }
     
     type Response = RpcGetTokenAccountBalanceResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.min_context_slot)
+    }
 }
 
 // getTokenAccountsByDelegate
@@ -66,6 +66,7 @@ pub struct RpcGetTokenAccountsByDelegateRequest {
 }
 
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TokenAccountsConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commitment: Option<Commitment>,
@@ -94,22 +95,39 @@ impl Serialize for RpcGetTokenAccountsByDelegateRequest {
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct TokenAccount {
-    pub account: serde_json::Value,
+    pub account: AccountInfo,
     pub pubkey: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetTokenAccountsByDelegateResponse {
-    pub value: Vec<TokenAccount>,
-    pub context: RpcContext,
+impl TokenAccount {
+    /// The token account fields, when requested with
+    /// [`Encoding::JsonParsed`]. Fails with
+    /// [`AccountDataError::NotTokenAccount`] for a parsed mint or multisig.
+    pub fn token_account(&self) -> Result<UiTokenAccount, AccountDataError> {
+        self.account
+            .parsed_account()?
+            .token_account()
+            .cloned()
+            .ok_or(AccountDataError::NotTokenAccount)
+    }
 }
 
+pub type RpcGetTokenAccountsByDelegateResponse = Response<Vec<TokenAccount>>;
+
 impl RpcRequest for RpcGetTokenAccountsByDelegateRequest {
     fn method_name() -> &'static str {
         "getTokenAccountsByDelegate"
     }
     
     type Response = RpcGetTokenAccountsByDelegateResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.min_context_slot)
+    }
 }
 
 // getTokenAccountsByOwner
@@ -132,11 +150,7 @@ impl Serialize for RpcGetTokenAccountsByOwnerRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetTokenAccountsByOwnerResponse {
-    pub value: Vec<TokenAccount>,
-    pub context: RpcContext,
-}
+pub type RpcGetTokenAccountsByOwnerResponse = Response<Vec<TokenAccount>>;
 
 impl RpcRequest for RpcGetTokenAccountsByOwnerRequest {
     fn method_name() -> &'static str {
@@ -144,6 +158,14 @@ impl RpcRequest for RpcGetTokenAccountsByOwnerRequest {
     }
     
     type Response = RpcGetTokenAccountsByOwnerResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.min_context_slot)
+    }
 }
 
 // getTokenLargestAccounts
@@ -169,26 +191,36 @@ impl Serialize for RpcGetTokenLargestAccountsRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TokenAccountBalance {
     pub address: String,
     pub amount: String,
     pub decimals: u8,
+    /// The amount as a float, which can round; see
+    /// [`TokenQuantity::to_f64_lossy`].
     pub ui_amount: Option<f64>,
     pub ui_amount_string: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetTokenLargestAccountsResponse {
-    pub value: Vec<TokenAccountBalance>,
-    pub context: RpcContext,
+impl TokenAccountBalance {
+    /// The exact balance.
+    pub fn quantity(&self) -> Result<TokenQuantity, AmountError> {
+        TokenQuantity::from_raw(&self.amount, self.decimals)
+    }
 }
 
+pub type RpcGetTokenLargestAccountsResponse = Response<Vec<TokenAccountBalance>>;
+
 impl RpcRequest for RpcGetTokenLargestAccountsRequest {
     fn method_name() -> &'static str {
         "getTokenLargestAccounts"
     }
     
     type Response = RpcGetTokenLargestAccountsResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
 }
 
 // getTokenSupply
@@ -210,6 +242,7 @@ impl Serialize for RpcGetTokenSupplyRequest {
         seq.serialize_element(&self.mint)?;
         if self.commitment.is_some() || self.min_context_slot.is_some() {
             #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
             struct Config {
                 #[serde(skip_serializing_if = "Option::is_none")]
                 commitment: Option<Commitment>,
@@ -226,25 +259,39 @@ impl Serialize for RpcGetTokenSupplyRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TokenSupply {
     pub amount: String,
     pub decimals: u8,
+    /// The amount as a float, which can round; see
+    /// [`TokenQuantity::to_f64_lossy`].
     pub ui_amount: Option<f64>,
     pub ui_amount_string: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetTokenSupplyResponse {
-    pub value: TokenSupply,
-    pub context: RpcContext,
+impl TokenSupply {
+    /// The exact supply.
+    pub fn quantity(&self) -> Result<TokenQuantity, AmountError> {
+        TokenQuantity::from_raw(&self.amount, self.decimals)
+    }
 }
 
+pub type RpcGetTokenSupplyResponse = Response<TokenSupply>;
+
 impl RpcRequest for RpcGetTokenSupplyRequest {
     fn method_name() -> &'static str {
         "getTokenSupply"
     }
     
     type Response = RpcGetTokenSupplyResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.min_context_slot)
+    }
 }
 
 
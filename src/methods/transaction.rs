@@ -1,15 +1,18 @@
 use crate::client::RpcRequest;
-use crate::types::{Commitment, RpcContext, Transaction, TransactionEncoding};
+use crate::types::{
+    Commitment, Response, Signature, Transaction, TransactionConfirmationStatus, TransactionEncoding,
+};
 use serde::{Deserialize, Serialize};
 
 // getTransaction
 #[derive(Debug, Clone)]
 pub struct RpcGetTransactionRequest {
-    pub signature: String,
+    pub signature: Signature,
     pub config: Option<TransactionConfig>,
 }
 
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct TransactionConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<TransactionEncoding>,
@@ -34,12 +37,7 @@ impl Serialize for RpcGetTransactionRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetTransactionResponse {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub value: Option<Transaction>,
-    pub context: RpcContext,
-}
+pub type RpcGetTransactionResponse = Response<Option<Transaction>>;
 
 impl RpcRequest for RpcGetTransactionRequest {
     fn method_name() -> &'static str {
@@ -66,6 +64,7 @@ impl Serialize for RpcGetTransactionCountRequest {
         let mut seq = serializer.serialize_seq(Some(len))?;
         if self.commitment.is_some() || self.min_context_slot.is_some() {
             #[derive(Serialize)]
+            #[serde(rename_all = "camelCase")]
             struct Config {
                 #[serde(skip_serializing_if = "Option::is_none")]
                 commitment: Option<Commitment>,
@@ -81,11 +80,7 @@ impl Serialize for RpcGetTransactionCountRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetTransactionCountResponse {
-    pub value: u64,
-    pub context: RpcContext,
-}
+pub type RpcGetTransactionCountResponse = Response<u64>;
 
 impl RpcRequest for RpcGetTransactionCountRequest {
     fn method_name() -> &'static str {
@@ -93,6 +88,10 @@ impl RpcRequest for RpcGetTransactionCountRequest {
     }
     
     type Response = RpcGetTransactionCountResponse;
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.min_context_slot)
+    }
 }
 
 // sendTransaction
@@ -102,7 +101,8 @@ pub struct RpcSendTransactionRequest {
     pub config: Option<SendTransactionConfig>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SendTransactionConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub skip_preflight: Option<bool>,
@@ -131,10 +131,7 @@ impl Serialize for RpcSendTransactionRequest {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcSendTransactionResponse {
-    pub value: String, // transaction signature
-}
+pub type RpcSendTransactionResponse = Response<Signature>;
 
 impl RpcRequest for RpcSendTransactionRequest {
     fn method_name() -> &'static str {
@@ -142,6 +139,10 @@ impl RpcRequest for RpcSendTransactionRequest {
     }
     
     type Response = RpcSendTransactionResponse;
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.get_or_insert_with(Default::default).min_context_slot)
+    }
 }
 
 // simulateTransaction
@@ -151,7 +152,8 @@ pub struct RpcSimulateTransactionRequest {
     pub config: Option<SimulateTransactionConfig>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SimulateTransactionConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sig_verify: Option<bool>,
@@ -190,6 +192,7 @@ impl Serialize for RpcSimulateTransactionRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SimulateTransactionResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub err: Option<serde_json::Value>,
@@ -203,11 +206,7 @@ pub struct SimulateTransactionResult {
     pub return_data: Option<serde_json::Value>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcSimulateTransactionResponse {
-    pub value: SimulateTransactionResult,
-    pub context: RpcContext,
-}
+pub type RpcSimulateTransactionResponse = Response<SimulateTransactionResult>;
 
 impl RpcRequest for RpcSimulateTransactionRequest {
     fn method_name() -> &'static str {
@@ -215,16 +214,25 @@ impl RpcRequest for RpcSimulateTransactionRequest {
     }
     
     type Response = RpcSimulateTransactionResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+
+    fn min_context_slot_mut(&mut self) -> Option<&mut Option<u64>> {
+        Some(&mut self.config.get_or_insert_with(Default::default).min_context_slot)
+    }
 }
 
 // getSignatureStatuses
 #[derive(Debug, Clone)]
 pub struct RpcGetSignatureStatusesRequest {
-    pub signatures: Vec<String>,
+    pub signatures: Vec<Signature>,
     pub config: Option<SignatureStatusesConfig>,
 }
 
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SignatureStatusesConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search_transaction_history: Option<bool>,
@@ -246,6 +254,7 @@ impl Serialize for RpcGetSignatureStatusesRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SignatureStatus {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub slot: Option<u64>,
@@ -254,14 +263,28 @@ pub struct SignatureStatus {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub err: Option<serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub confirmation_status: Option<String>,
+    pub confirmation_status: Option<TransactionConfirmationStatus>,
+}
+
+impl SignatureStatus {
+    /// Whether the transaction has reached `commitment`. This says nothing
+    /// about whether it succeeded; check `err` for that.
+    ///
+    /// Nodes that predate `confirmationStatus` report a finalized
+    /// transaction by leaving `confirmations` empty.
+    pub fn satisfies(&self, commitment: Commitment) -> bool {
+        if let Some(status) = self.confirmation_status {
+            return status.satisfies(commitment);
+        }
+        match commitment {
+            Commitment::Processed => true,
+            Commitment::Confirmed => self.confirmations.is_none_or(|confirmations| confirmations > 1),
+            Commitment::Finalized => self.confirmations.is_none(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetSignatureStatusesResponse {
-    pub value: Vec<Option<SignatureStatus>>,
-    pub context: RpcContext,
-}
+pub type RpcGetSignatureStatusesResponse = Response<Vec<Option<SignatureStatus>>>;
 
 impl RpcRequest for RpcGetSignatureStatusesRequest {
     fn method_name() -> &'static str {
@@ -269,6 +292,10 @@ impl RpcRequest for RpcGetSignatureStatusesRequest {
     }
     
     type Response = RpcGetSignatureStatusesResponse;
+
+    fn has_context(&self) -> bool {
+        true
+    }
 }
 
 // getRecentPrioritizationFees
@@ -293,15 +320,13 @@ impl Serialize for RpcGetRecentPrioritizationFeesRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct PrioritizationFee {
     pub slot: u64,
     pub prioritization_fee: u64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetRecentPrioritizationFeesResponse {
-    pub value: Vec<PrioritizationFee>,
-}
+pub type RpcGetRecentPrioritizationFeesResponse = Response<Vec<PrioritizationFee>>;
 
 impl RpcRequest for RpcGetRecentPrioritizationFeesRequest {
     fn method_name() -> &'static str {
@@ -311,4 +336,37 @@ impl RpcRequest for RpcGetRecentPrioritizationFeesRequest {
     type Response = RpcGetRecentPrioritizationFeesResponse;
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
 
+    fn status(value: serde_json::Value) -> SignatureStatus {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn satisfies_uses_the_confirmation_status() {
+        let confirmed = status(json!({ "slot": 5, "confirmations": 3, "err": null, "confirmationStatus": "confirmed" }));
+        assert!(confirmed.satisfies(Commitment::Confirmed));
+        assert!(!confirmed.satisfies(Commitment::Finalized));
+
+        // A failed transaction has still reached its commitment.
+        let failed = status(json!({ "slot": 5, "confirmations": null, "err": { "InstructionError": [0, "InvalidArgument"] }, "confirmationStatus": "finalized" }));
+        assert!(failed.satisfies(Commitment::Finalized));
+    }
+
+    #[test]
+    fn satisfies_falls_back_to_confirmations() {
+        let processed = status(json!({ "slot": 5, "confirmations": 1, "err": null }));
+        assert!(processed.satisfies(Commitment::Processed));
+        assert!(!processed.satisfies(Commitment::Confirmed));
+
+        let confirmed = status(json!({ "slot": 5, "confirmations": 2, "err": null }));
+        assert!(confirmed.satisfies(Commitment::Confirmed));
+        assert!(!confirmed.satisfies(Commitment::Finalized));
+
+        let rooted = status(json!({ "slot": 5, "confirmations": null, "err": null }));
+        assert!(rooted.satisfies(Commitment::Finalized));
+    }
+}
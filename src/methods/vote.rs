@@ -1,5 +1,5 @@
 use crate::client::RpcRequest;
-use crate::types::{Commitment, RpcContext};
+use crate::types::{Commitment, Lamports, Response};
 use serde::{Deserialize, Serialize};
 
 // getVoteAccounts
@@ -9,6 +9,7 @@ pub struct RpcGetVoteAccountsRequest {
 }
 
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct VoteAccountsConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commitment: Option<Commitment>,
@@ -36,10 +37,11 @@ impl Serialize for RpcGetVoteAccountsRequest {
 }
 
 #[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct VoteAccount {
     pub vote_pubkey: String,
     pub node_pubkey: String,
-    pub activated_stake: u64,
+    pub activated_stake: Lamports,
     pub epoch_vote_account: bool,
     pub epoch_credits: u64,
     pub commission: u8,
@@ -53,11 +55,7 @@ pub struct VoteAccounts {
     pub delinquent: Vec<VoteAccount>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct RpcGetVoteAccountsResponse {
-    pub value: VoteAccounts,
-    pub context: RpcContext,
-}
+pub type RpcGetVoteAccountsResponse = Response<VoteAccounts>;
 
 impl RpcRequest for RpcGetVoteAccountsRequest {
     fn method_name() -> &'static str {
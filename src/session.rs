@@ -0,0 +1,87 @@
+use crate::client::{CallOptions, JsonRpcClient, RpcRequest};
+use crate::error::Result;
+use crate::types::RpcResponse;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Reads that never observe state older than a previous read.
+///
+/// A session remembers the highest `context.slot` seen in any response and
+/// sets `min_context_slot` on every later request that accepts one, so a node
+/// that is behind answers with an error instead of stale data. Sessions made
+/// with [`Session::with_client`] share the same slot, which keeps reads
+/// monotonic across endpoints.
+#[derive(Debug, Clone)]
+pub struct Session {
+    client: JsonRpcClient,
+    slot: Arc<AtomicU64>,
+}
+
+impl Session {
+    pub fn new(client: JsonRpcClient) -> Self {
+        Self {
+            client,
+            slot: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// A session on another client that shares this session's slot.
+    pub fn with_client(&self, client: JsonRpcClient) -> Self {
+        Self {
+            client,
+            slot: self.slot.clone(),
+        }
+    }
+
+    pub fn client(&self) -> &JsonRpcClient {
+        &self.client
+    }
+
+    /// The highest context slot observed so far.
+    pub fn slot(&self) -> Option<u64> {
+        match self.slot.load(Ordering::Acquire) {
+            0 => None,
+            slot => Some(slot),
+        }
+    }
+
+    /// Raises the session's slot, e.g. to the slot a transaction landed in.
+    pub fn observe(&self, slot: u64) {
+        self.slot.fetch_max(slot, Ordering::AcqRel);
+    }
+
+    pub async fn call<R>(&self, request: R) -> Result<R::Response>
+    where
+        R: RpcRequest,
+    {
+        self.call_with_options(request, CallOptions::new()).await
+    }
+
+    /// Like [`Session::call`], with a deadline or cancellation token.
+    ///
+    /// A `min_context_slot` already set on `request` is kept if it is higher
+    /// than the session's slot.
+    pub async fn call_with_options<R>(&self, mut request: R, options: CallOptions) -> Result<R::Response>
+    where
+        R: RpcRequest,
+    {
+        if let Some(slot) = self.slot() {
+            if let Some(min_context_slot) = request.min_context_slot_mut() {
+                *min_context_slot = Some(min_context_slot.map_or(slot, |min| min.max(slot)));
+            }
+        }
+
+        let response = self.client.call_with_options(request, options).await?;
+        if let Some(slot) = response.slot() {
+            self.observe(slot);
+        }
+        Ok(response)
+    }
+}
+
+impl JsonRpcClient {
+    /// Starts a [`Session`] on this client.
+    pub fn session(&self) -> Session {
+        Session::new(self.clone())
+    }
+}
@@ -0,0 +1,495 @@
+use crate::client::{check_response, JsonRpcClient, JsonRpcErrorResponse, RpcRequest};
+use crate::error::{JsonRpcError, Result};
+use crate::id::RequestId;
+#[cfg(feature = "instrumentation")]
+use crate::instrument;
+use crate::limit::RatePermit;
+use crate::methods::program::{ProgramAccount, RpcGetProgramAccountsRequest};
+use futures::stream::{self, Stream};
+use serde::de::DeserializeOwned;
+#[cfg(feature = "instrumentation")]
+use tracing::Instrument;
+
+impl JsonRpcClient {
+    /// Sends a `getProgramAccounts` request and yields its accounts as they
+    /// arrive, without buffering the whole response.
+    ///
+    /// Only one account is held in memory at a time, so the max response size
+    /// set with [`JsonRpcClient::with_max_response_size`] does not apply. The
+    /// response id is checked once the body has been read, so a mismatch is
+    /// reported as the last item of the stream.
+    pub async fn stream_program_accounts(
+        &self,
+        request: RpcGetProgramAccountsRequest,
+    ) -> Result<impl Stream<Item = Result<ProgramAccount>>> {
+        let method = RpcGetProgramAccountsRequest::method_name();
+        #[cfg(feature = "instrumentation")]
+        let call = instrument::Call::start(method, self.url());
+
+        let setup = async {
+            let params = serde_json::to_value(&request)?;
+            let permit = self.permit(method).await;
+            let (id, body) = self.encode(method, params)?;
+            #[cfg(feature = "instrumentation")]
+            instrument::sent(method, &id, body.len());
+            // Streams are not held to the max response size, even when a
+            // cassette buffers them.
+            let response = self.post(body, None).await?;
+            Ok((permit, id, response))
+        };
+        #[cfg(feature = "instrumentation")]
+        let setup = setup.instrument(call.span().clone());
+        let setup: Result<_> = setup.await;
+
+        #[cfg(feature = "instrumentation")]
+        if let Err(err) = &setup {
+            call.finish(Some(err));
+        }
+        let (permit, id, response) = setup?;
+
+        let state = StreamState {
+            response,
+            scanner: Scanner::default(),
+            id,
+            _permit: permit,
+            finished: false,
+            #[cfg(feature = "instrumentation")]
+            call,
+        };
+
+        Ok(stream::unfold(state, |mut state| async move {
+            if state.finished {
+                return None;
+            }
+            #[cfg(feature = "instrumentation")]
+            let item = {
+                let span = state.call.span().clone();
+                let item = state.next().instrument(span).await;
+                match &item {
+                    Some(Ok(_)) => {}
+                    Some(Err(err)) => state.call.finish(Some(err)),
+                    None => state.call.finish(None),
+                }
+                item
+            };
+            #[cfg(not(feature = "instrumentation"))]
+            let item = state.next().await;
+
+            match item {
+                Some(Ok(account)) => Some((Ok(account), state)),
+                Some(Err(err)) => {
+                    state.finished = true;
+                    Some((Err(err), state))
+                }
+                None => None,
+            }
+        }))
+    }
+}
+
+struct StreamState {
+    response: reqwest::Response,
+    scanner: Scanner,
+    id: RequestId,
+    _permit: Option<RatePermit>,
+    finished: bool,
+    #[cfg(feature = "instrumentation")]
+    call: instrument::Call,
+}
+
+impl StreamState {
+    async fn next(&mut self) -> Option<Result<ProgramAccount>> {
+        loop {
+            match self.scanner.next::<ProgramAccount>() {
+                Ok(Scan::Item(account)) => return Some(Ok(account)),
+                Ok(Scan::Done) => return self.scanner.finish(&self.id).err().map(Err),
+                Ok(Scan::NeedMore) => {}
+                Err(err) => return Some(Err(err)),
+            }
+
+            match self.response.chunk().await {
+                Ok(Some(chunk)) => {
+                    #[cfg(feature = "instrumentation")]
+                    self.call.received(chunk.len());
+                    self.scanner.feed(&chunk);
+                }
+                Ok(None) => {
+                    return Some(Err(JsonRpcError::InvalidResponse(
+                        "response ended before the JSON-RPC envelope was closed".to_string(),
+                    )))
+                }
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+    }
+}
+
+enum Scan<T> {
+    Item(T),
+    NeedMore,
+    Done,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frame {
+    /// The top-level `{"jsonrpc": .., "result": .., "id": ..}` object.
+    Envelope,
+    /// A `{"context": .., "value": [..]}` result, sent when `withContext` is set.
+    Context,
+    /// The array of accounts.
+    Accounts,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    Open,
+    First,
+    Next,
+}
+
+/// Incremental scanner that picks the elements of the result array out of a
+/// JSON-RPC response as bytes arrive.
+///
+/// Element boundaries are found by tracking nesting and string literals only;
+/// each element is then handed to `serde_json` as a complete slice.
+#[derive(Debug)]
+struct Scanner {
+    buf: Vec<u8>,
+    pos: usize,
+    stack: Vec<(Frame, Expect)>,
+    found_result: bool,
+    id: Option<Vec<u8>>,
+    error: Option<Vec<u8>>,
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Self {
+            buf: Vec::new(),
+            pos: 0,
+            stack: vec![(Frame::Envelope, Expect::Open)],
+            found_result: false,
+            id: None,
+            error: None,
+        }
+    }
+}
+
+impl Scanner {
+    fn feed(&mut self, chunk: &[u8]) {
+        if self.pos > 0 {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+        self.buf.extend_from_slice(chunk);
+    }
+
+    fn next<T: DeserializeOwned>(&mut self) -> Result<Scan<T>> {
+        loop {
+            self.skip_whitespace();
+            let Some(&(frame, expect)) = self.stack.last() else {
+                return Ok(Scan::Done);
+            };
+            let Some(&byte) = self.buf.get(self.pos) else {
+                return Ok(Scan::NeedMore);
+            };
+
+            match (frame, expect) {
+                (Frame::Accounts, Expect::Open) => {
+                    self.expect_byte(byte, b'[')?;
+                    self.set_expect(Expect::First);
+                }
+                (_, Expect::Open) => {
+                    self.expect_byte(byte, b'{')?;
+                    self.set_expect(Expect::First);
+                }
+                (Frame::Accounts, _) if byte == b']' => self.close(),
+                (_, _) if byte == b'}' => self.close(),
+                (Frame::Accounts, expect) => {
+                    let start = self.pos;
+                    let Some(item_start) = self.after_separator(expect)? else {
+                        self.pos = start;
+                        return Ok(Scan::NeedMore);
+                    };
+                    let Some(end) = value_end(&self.buf, item_start) else {
+                        self.pos = start;
+                        return Ok(Scan::NeedMore);
+                    };
+                    let item = serde_json::from_slice(&self.buf[item_start..end])?;
+                    self.pos = end;
+                    self.set_expect(Expect::Next);
+                    return Ok(Scan::Item(item));
+                }
+                (frame, expect) => {
+                    let start = self.pos;
+                    if !self.member(frame, expect)? {
+                        self.pos = start;
+                        return Ok(Scan::NeedMore);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks the envelope once it has been fully read.
+    fn finish(&mut self, id: &RequestId) -> Result<()> {
+        let response_id = match &self.id {
+            Some(raw) => serde_json::from_slice(raw)?,
+            None => None,
+        };
+        let error: Option<JsonRpcErrorResponse> = match &self.error {
+            Some(raw) => serde_json::from_slice(raw)?,
+            None => None,
+        };
+        check_response(id, response_id, error)?;
+
+        if !self.found_result {
+            return Err(JsonRpcError::MissingResult);
+        }
+        Ok(())
+    }
+
+    // Reads one `"key": value` member of an object frame. Returns `false` if
+    // the buffer does not hold enough of it yet.
+    fn member(&mut self, frame: Frame, expect: Expect) -> Result<bool> {
+        let Some(key_start) = self.after_separator(expect)? else {
+            return Ok(false);
+        };
+        if self.buf[key_start] != b'"' {
+            return Err(unexpected(self.buf[key_start]));
+        }
+        let Some(key_end) = string_end(&self.buf, key_start) else {
+            return Ok(false);
+        };
+        let key: String = serde_json::from_slice(&self.buf[key_start..key_end])?;
+
+        self.pos = key_end;
+        self.skip_whitespace();
+        let Some(&colon) = self.buf.get(self.pos) else {
+            return Ok(false);
+        };
+        self.expect_byte(colon, b':')?;
+        self.skip_whitespace();
+        let Some(&value) = self.buf.get(self.pos) else {
+            return Ok(false);
+        };
+
+        let nested = match (frame, key.as_str(), value) {
+            (Frame::Envelope, "result", b'[') => Some(Frame::Accounts),
+            (Frame::Envelope, "result", b'{') => Some(Frame::Context),
+            (Frame::Context, "value", b'[') => Some(Frame::Accounts),
+            _ => None,
+        };
+
+        if let Some(nested) = nested {
+            self.found_result = true;
+            self.set_expect(Expect::Next);
+            self.stack.push((nested, Expect::Open));
+            return Ok(true);
+        }
+
+        let Some(end) = value_end(&self.buf, self.pos) else {
+            return Ok(false);
+        };
+        self.set_expect(Expect::Next);
+        if frame == Frame::Envelope {
+            let raw = self.buf[self.pos..end].to_vec();
+            match key.as_str() {
+                "id" => self.id = Some(raw),
+                "error" => self.error = Some(raw),
+                _ => {}
+            }
+        }
+        self.pos = end;
+        Ok(true)
+    }
+
+    // Consumes the `,` before every member or element but the first, and
+    // returns the position the member or element starts at.
+    fn after_separator(&mut self, expect: Expect) -> Result<Option<usize>> {
+        if expect == Expect::Next {
+            let byte = self.buf[self.pos];
+            self.expect_byte(byte, b',')?;
+            self.skip_whitespace();
+        }
+        Ok(self.buf.get(self.pos).map(|_| self.pos))
+    }
+
+    fn close(&mut self) {
+        self.pos += 1;
+        self.stack.pop();
+    }
+
+    fn set_expect(&mut self, expect: Expect) {
+        if let Some(top) = self.stack.last_mut() {
+            top.1 = expect;
+        }
+    }
+
+    fn expect_byte(&mut self, byte: u8, expected: u8) -> Result<()> {
+        if byte != expected {
+            return Err(unexpected(byte));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.buf.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+}
+
+fn unexpected(byte: u8) -> JsonRpcError {
+    JsonRpcError::InvalidResponse(format!(
+        "unexpected character {:?} in response",
+        byte as char
+    ))
+}
+
+// Returns the position just past the JSON value starting at `start`, or `None`
+// if the buffer ends before the value does.
+fn value_end(buf: &[u8], start: usize) -> Option<usize> {
+    match buf[start] {
+        b'"' => string_end(buf, start),
+        b'{' | b'[' => {
+            let mut depth = 0usize;
+            let mut i = start;
+            while i < buf.len() {
+                match buf[i] {
+                    b'"' => {
+                        i = string_end(buf, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            None
+        }
+        // Numbers, `true`, `false` and `null` end at the next delimiter.
+        _ => buf[start..]
+            .iter()
+            .position(|b| matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace())
+            .map(|len| start + len),
+    }
+}
+
+fn string_end(buf: &[u8], start: usize) -> Option<usize> {
+    let mut i = start + 1;
+    while i < buf.len() {
+        match buf[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    // Feeds `body` in chunks of `chunk_size` bytes and collects the items.
+    fn scan(body: &str, chunk_size: usize) -> Result<Vec<Value>> {
+        let mut scanner = Scanner::default();
+        let mut items = Vec::new();
+        let mut chunks = body.as_bytes().chunks(chunk_size);
+        loop {
+            match scanner.next::<Value>()? {
+                Scan::Item(item) => items.push(item),
+                Scan::Done => {
+                    scanner.finish(&RequestId::from(1))?;
+                    return Ok(items);
+                }
+                Scan::NeedMore => match chunks.next() {
+                    Some(chunk) => scanner.feed(chunk),
+                    None => return Err(JsonRpcError::InvalidResponse("truncated".to_string())),
+                },
+            }
+        }
+    }
+
+    fn scan_all_chunk_sizes(body: &str) -> Vec<Value> {
+        let expected = scan(body, body.len()).unwrap();
+        for chunk_size in 1..body.len() {
+            assert_eq!(scan(body, chunk_size).unwrap(), expected, "chunk size {}", chunk_size);
+        }
+        expected
+    }
+
+    #[test]
+    fn scans_bare_result_array() {
+        let body = r#"{"jsonrpc":"2.0","result":[{"pubkey":"a","n":1}, {"pubkey":"b","n":[2,{"x":null}]}],"id":1}"#;
+        assert_eq!(
+            scan_all_chunk_sizes(body),
+            vec![json!({"pubkey": "a", "n": 1}), json!({"pubkey": "b", "n": [2, {"x": null}]})]
+        );
+    }
+
+    #[test]
+    fn scans_result_with_context() {
+        let body = r#"{"jsonrpc":"2.0","result":{"context":{"slot":5},"value":[1, true, "s"]},"id":1}"#;
+        assert_eq!(scan_all_chunk_sizes(body), vec![json!(1), json!(true), json!("s")]);
+    }
+
+    #[test]
+    fn handles_brackets_and_escapes_in_strings() {
+        let body = r#"{ "id" : 1 , "result" : [ "]}\"[{" , { "k": "\\" } ] , "jsonrpc" : "2.0" }"#;
+        assert_eq!(scan_all_chunk_sizes(body), vec![json!("]}\"[{"), json!({"k": "\\"})]);
+    }
+
+    #[test]
+    fn scans_empty_result() {
+        let body = r#"{"jsonrpc":"2.0","result":[],"id":1}"#;
+        assert!(scan_all_chunk_sizes(body).is_empty());
+    }
+
+    #[test]
+    fn reports_rpc_errors() {
+        let body = r#"{"jsonrpc":"2.0","error":{"code":-32010,"message":"excluded"},"id":1}"#;
+        let err = scan(body, 7).unwrap_err();
+        assert!(matches!(err, JsonRpcError::Rpc { code: -32010, .. }));
+    }
+
+    #[test]
+    fn checks_the_id_after_the_result() {
+        let body = r#"{"jsonrpc":"2.0","result":[1],"id":2}"#;
+        assert!(matches!(scan(body, 3), Err(JsonRpcError::IdMismatch { .. })));
+    }
+
+    #[test]
+    fn requires_a_result() {
+        let body = r#"{"jsonrpc":"2.0","id":1}"#;
+        assert!(matches!(scan(body, 3), Err(JsonRpcError::MissingResult)));
+    }
+
+    #[test]
+    fn rejects_malformed_bodies() {
+        assert!(matches!(scan("[1]", 1), Err(JsonRpcError::InvalidResponse(_))));
+        assert!(matches!(
+            scan(r#"{"result":[1 2],"id":1}"#, 4),
+            Err(JsonRpcError::InvalidResponse(_))
+        ));
+        assert!(scan(r#"{"result":[1,"#, 4).is_err());
+    }
+
+    #[test]
+    fn finds_value_ends() {
+        assert_eq!(value_end(b"123,", 0), Some(3));
+        assert_eq!(value_end(b"123", 0), None);
+        assert_eq!(value_end(br#""a\"b" "#, 0), Some(6));
+        assert_eq!(value_end(br#"{"a":[1,"]"]}x"#, 0), Some(13));
+        assert_eq!(value_end(br#"{"a":[1"#, 0), None);
+    }
+}
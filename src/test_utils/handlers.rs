@@ -0,0 +1,811 @@
+use super::ledger::{Ledger, MockAccount, MockSignatureStatus};
+use super::{Fault, MAX_LOCKOUT_HISTORY};
+use crate::types::TokenQuantity;
+use base64::prelude::{Engine, BASE64_STANDARD};
+use serde_json::{json, Map, Value};
+
+const API_VERSION: &str = "2.0.0";
+
+const INVALID_REQUEST: i64 = -32600;
+const INVALID_PARAMS: i64 = -32602;
+const BLOCK_NOT_AVAILABLE: i64 = -32004;
+const MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32016;
+
+const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const STAKE_PROGRAM_ID: &str = "Stake11111111111111111111111111111111111111";
+
+const LAMPORTS_PER_SIGNATURE: u64 = 5000;
+// Half of every fee is burned, the rest paid to the slot leader.
+const BURN_PERCENT: u64 = 50;
+const MAX_GET_BLOCKS_RANGE: u64 = 500_000;
+const MAX_LARGEST_ACCOUNTS: usize = 20;
+const RECENT_PRIORITIZATION_FEE_SLOTS: u64 = 150;
+const SLOTS_PER_YEAR: u64 = 78_892_314;
+
+const INFLATION_INITIAL: f64 = 0.08;
+const INFLATION_TERMINAL: f64 = 0.015;
+const INFLATION_TAPER: f64 = 0.15;
+const INFLATION_FOUNDATION: f64 = 0.05;
+const INFLATION_FOUNDATION_TERM: f64 = 7.0;
+
+type HandlerResult = std::result::Result<Value, Fault>;
+
+/// Serves `method` from the ledger. Returns `None` for methods the mock does
+/// not implement.
+pub(super) fn handle(ledger: &mut Ledger, method: &str, params: &Value) -> Option<HandlerResult> {
+    let result = match method {
+        "getAccountInfo" => get_account_info(ledger, params),
+        "getBalance" => get_balance(ledger, params),
+        "getMultipleAccounts" => get_multiple_accounts(ledger, params),
+        "getProgramAccounts" => get_program_accounts(ledger, params),
+        "getMinimumBalanceForRentExemption" => get_minimum_balance_for_rent_exemption(params),
+        "getSlot" => with_min_context_slot(ledger, params, 0, |ledger| ledger.slot.into()),
+        "getBlockHeight" => with_min_context_slot(ledger, params, 0, |ledger| ledger.block_height.into()),
+        "getTransactionCount" => {
+            with_min_context_slot(ledger, params, 0, |ledger| ledger.transaction_count.into())
+        }
+        "getLatestBlockhash" => get_latest_blockhash(ledger, params),
+        "isBlockhashValid" => is_blockhash_valid(ledger, params),
+        "getSignatureStatuses" => get_signature_statuses(ledger, params),
+        "sendTransaction" => send_transaction(ledger, params),
+        "simulateTransaction" => simulate_transaction(ledger, params),
+        "getEpochInfo" => get_epoch_info(ledger, params),
+        "getEpochSchedule" => Ok(json!({
+            "slotsPerEpoch": ledger.slots_per_epoch(),
+            "leaderScheduleSlotOffset": ledger.slots_per_epoch(),
+            "warmup": false,
+            "firstNormalEpoch": 0,
+            "firstNormalSlot": 0,
+        })),
+        "getGenesisHash" => Ok(ledger.genesis_hash.clone().into()),
+        "getIdentity" => Ok(json!({ "identity": ledger.identity })),
+        "getFirstAvailableBlock" => Ok(0.into()),
+        "getHealth" => Ok("ok".into()),
+        "getVersion" => Ok(json!({ "solana-core": API_VERSION, "feature-set": 0 })),
+        "getBlock" => get_block(ledger, params),
+        "getBlocks" => get_blocks(ledger, params),
+        "getTransaction" => get_transaction(ledger, params),
+        "getRecentPrioritizationFees" => get_recent_prioritization_fees(ledger, params),
+        "getClusterNodes" => Ok(json!([{
+            "pubkey": ledger.identity,
+            "gossip": "127.0.0.1:8001",
+            "tpu": "127.0.0.1:8003",
+            "rpc": null,
+            "version": API_VERSION,
+            "featureSet": 0,
+            "shredVersion": 0,
+        }])),
+        "getSlotLeader" => with_min_context_slot(ledger, params, 0, |ledger| ledger.identity.clone().into()),
+        "getLeaderSchedule" => get_leader_schedule(ledger, params),
+        "getMaxRetransmitSlot" | "getMaxShredInsertSlot" => Ok(ledger.slot.into()),
+        "getVoteAccounts" => get_vote_accounts(ledger, params),
+        "getStakeActivation" => get_stake_activation(ledger, params),
+        "getInflationGovernor" => Ok(json!({
+            "initial": INFLATION_INITIAL,
+            "terminal": INFLATION_TERMINAL,
+            "taper": INFLATION_TAPER,
+            "foundation": INFLATION_FOUNDATION,
+            "foundationTerm": INFLATION_FOUNDATION_TERM,
+        })),
+        "getInflationRate" => Ok(inflation_rate(ledger)),
+        "getInflationReward" => get_inflation_reward(ledger, params),
+        "getLargestAccounts" => get_largest_accounts(ledger, params),
+        "getSupply" => get_supply(ledger, params),
+        "getTokenAccountBalance" => get_token_account_balance(ledger, params),
+        "getTokenAccountsByOwner" => get_token_accounts(ledger, params, |account| Some(&account.owner)),
+        "getTokenAccountsByDelegate" => get_token_accounts(ledger, params, |account| account.delegate.as_ref()),
+        "getTokenLargestAccounts" => get_token_largest_accounts(ledger, params),
+        "getTokenSupply" => get_token_supply(ledger, params),
+        _ => return None,
+    };
+    Some(result)
+}
+
+fn get_account_info(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let pubkey = string_param(params, 0)?;
+    let config = config(params, 1);
+    check_min_context_slot(ledger, config)?;
+
+    let value = match ledger.accounts.get(pubkey) {
+        Some(account) => encode_account(account, config)?,
+        None => Value::Null,
+    };
+    Ok(with_context(ledger, value))
+}
+
+fn get_balance(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let pubkey = string_param(params, 0)?;
+    check_min_context_slot(ledger, config(params, 1))?;
+    Ok(with_context(ledger, ledger.balance(pubkey).into()))
+}
+
+fn get_multiple_accounts(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let pubkeys = params
+        .get(0)
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid_params("expected an array of pubkeys"))?;
+    if pubkeys.len() > 100 {
+        return Err(invalid_params("Too many inputs provided; max 100"));
+    }
+    let config = config(params, 1);
+    check_min_context_slot(ledger, config)?;
+
+    let accounts = pubkeys
+        .iter()
+        .map(|pubkey| {
+            let pubkey = pubkey.as_str().ok_or_else(|| invalid_params("invalid pubkey"))?;
+            match ledger.accounts.get(pubkey) {
+                Some(account) => encode_account(account, config),
+                None => Ok(Value::Null),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(with_context(ledger, accounts.into()))
+}
+
+fn get_program_accounts(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let program_id = string_param(params, 0)?;
+    let config = config(params, 1);
+    check_min_context_slot(ledger, config)?;
+
+    let filters = config
+        .get("filters")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    if filters.len() > 4 {
+        return Err(invalid_params("Too many filters provided; max 4"));
+    }
+
+    let mut accounts = Vec::new();
+    for (pubkey, account) in &ledger.accounts {
+        if account.owner != program_id || !matches_filters(account, filters)? {
+            continue;
+        }
+        accounts.push(json!({
+            "pubkey": pubkey,
+            "account": encode_account(account, config)?,
+        }));
+    }
+
+    if config.get("withContext").and_then(Value::as_bool).unwrap_or(false) {
+        Ok(with_context(ledger, accounts.into()))
+    } else {
+        Ok(accounts.into())
+    }
+}
+
+fn get_minimum_balance_for_rent_exemption(params: &Value) -> HandlerResult {
+    const ACCOUNT_STORAGE_OVERHEAD: u64 = 128;
+    const LAMPORTS_PER_BYTE_YEAR: u64 = 3480;
+    const EXEMPTION_THRESHOLD_YEARS: u64 = 2;
+
+    let data_len = params
+        .get(0)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| invalid_params("expected a data length"))?;
+    Ok(((ACCOUNT_STORAGE_OVERHEAD + data_len) * LAMPORTS_PER_BYTE_YEAR * EXEMPTION_THRESHOLD_YEARS).into())
+}
+
+fn get_latest_blockhash(ledger: &Ledger, params: &Value) -> HandlerResult {
+    check_min_context_slot(ledger, config(params, 0))?;
+    Ok(with_context(
+        ledger,
+        json!({
+            "blockhash": ledger.latest_blockhash(),
+            "lastValidBlockHeight": ledger.last_valid_block_height(),
+        }),
+    ))
+}
+
+fn is_blockhash_valid(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let blockhash = string_param(params, 0)?;
+    check_min_context_slot(ledger, config(params, 1))?;
+    Ok(with_context(ledger, ledger.is_blockhash_valid(blockhash).into()))
+}
+
+fn get_signature_statuses(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let signatures = params
+        .get(0)
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid_params("expected an array of signatures"))?;
+    if signatures.len() > 256 {
+        return Err(invalid_params("Too many inputs provided; max 256"));
+    }
+
+    let statuses = signatures
+        .iter()
+        .map(|signature| {
+            signature
+                .as_str()
+                .and_then(|signature| ledger.signature_status(signature))
+                .unwrap_or(Value::Null)
+        })
+        .collect::<Vec<_>>();
+    Ok(with_context(ledger, statuses.into()))
+}
+
+fn send_transaction(ledger: &mut Ledger, params: &Value) -> HandlerResult {
+    let signature = transaction_signature(params)?;
+    ledger.land_transaction(signature.clone());
+    Ok(signature.into())
+}
+
+fn simulate_transaction(ledger: &Ledger, params: &Value) -> HandlerResult {
+    transaction_signature(params)?;
+    Ok(with_context(
+        ledger,
+        json!({
+            "err": null,
+            "logs": [],
+            "accounts": null,
+            "unitsConsumed": 0,
+            "returnData": null,
+        }),
+    ))
+}
+
+fn get_epoch_info(ledger: &Ledger, params: &Value) -> HandlerResult {
+    check_min_context_slot(ledger, config(params, 0))?;
+    Ok(json!({
+        "absoluteSlot": ledger.slot,
+        "blockHeight": ledger.block_height,
+        "epoch": ledger.epoch(),
+        "slotIndex": ledger.slot_index(),
+        "slotsInEpoch": ledger.slots_per_epoch(),
+        "transactionCount": ledger.transaction_count,
+    }))
+}
+
+// The mock keeps signatures rather than transactions, so a transaction is
+// served as just its signature.
+fn get_block(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let slot = params
+        .get(0)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| invalid_params("expected a slot"))?;
+    let config = config(params, 1);
+    let blockhash = ledger.blockhash(slot).ok_or_else(|| {
+        Fault::rpc(BLOCK_NOT_AVAILABLE, format!("Block not available for slot {}", slot))
+    })?;
+    let previous_blockhash = match slot.checked_sub(1) {
+        Some(parent) => ledger.blockhash(parent),
+        None => Some(ledger.genesis_hash.clone()),
+    };
+
+    let signatures = landed_signatures(ledger, slot);
+    let mut block = json!({
+        "blockhash": blockhash,
+        "previousBlockhash": previous_blockhash,
+        "parentSlot": slot.saturating_sub(1),
+        "blockTime": null,
+        "blockHeight": ledger.block_height.saturating_sub(ledger.slot - slot),
+    });
+    match config.get("transactionDetails").and_then(Value::as_str).unwrap_or("full") {
+        "full" => {
+            block["transactions"] = signatures
+                .iter()
+                .map(|(signature, status)| {
+                    json!({
+                        "transaction": { "signatures": [signature] },
+                        "meta": transaction_meta(status),
+                    })
+                })
+                .collect();
+        }
+        "signatures" => {
+            block["signatures"] = signatures.iter().map(|(signature, _)| *signature).collect();
+        }
+        "none" => {}
+        other => return Err(invalid_params(&format!("unsupported transactionDetails {}", other))),
+    }
+    if config.get("rewards").and_then(Value::as_bool).unwrap_or(true) {
+        let fees = signatures.len() as u64 * LAMPORTS_PER_SIGNATURE;
+        let leader_fees = fees - fees * BURN_PERCENT / 100;
+        block["rewards"] = if leader_fees == 0 {
+            json!([])
+        } else {
+            json!([{
+                "pubkey": ledger.identity,
+                "lamports": leader_fees,
+                "postBalance": ledger.balance(&ledger.identity),
+                "rewardType": "Fee",
+                "commission": null,
+            }])
+        };
+    }
+    Ok(block)
+}
+
+fn get_blocks(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let start_slot = params
+        .get(0)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| invalid_params("expected a start slot"))?;
+    let end_slot = params.get(1).and_then(Value::as_u64).unwrap_or(ledger.slot);
+    if end_slot.saturating_sub(start_slot) > MAX_GET_BLOCKS_RANGE {
+        return Err(invalid_params(&format!("Slot range too large; max {}", MAX_GET_BLOCKS_RANGE)));
+    }
+    let slots = (start_slot..=end_slot.min(ledger.slot)).collect::<Vec<_>>();
+    Ok(slots.into())
+}
+
+fn get_transaction(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let signature = string_param(params, 0)?;
+    let status = match ledger.signatures.get(signature) {
+        Some(status) if status.slot <= ledger.slot => status,
+        _ => return Ok(Value::Null),
+    };
+    Ok(json!({
+        "slot": status.slot,
+        "transaction": { "signatures": [signature] },
+        "meta": transaction_meta(status),
+        "blockTime": null,
+    }))
+}
+
+fn get_recent_prioritization_fees(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let addresses = params.get(0).and_then(Value::as_array).map_or(0, Vec::len);
+    if addresses > 128 {
+        return Err(invalid_params("Too many inputs provided; max 128"));
+    }
+    let first_slot = ledger.slot.saturating_sub(RECENT_PRIORITIZATION_FEE_SLOTS - 1);
+    let fees = (first_slot..=ledger.slot)
+        .map(|slot| json!({ "slot": slot, "prioritizationFee": 0 }))
+        .collect::<Vec<_>>();
+    Ok(fees.into())
+}
+
+// The ledger's identity leads every slot.
+fn get_leader_schedule(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let (slot, config) = match params.get(0) {
+        Some(Value::Object(_)) => (None, config(params, 0)),
+        slot => (slot.and_then(Value::as_u64), config(params, 1)),
+    };
+    let epoch = slot.unwrap_or(ledger.slot) / ledger.slots_per_epoch();
+    if epoch > ledger.epoch() + 1 {
+        return Ok(Value::Null);
+    }
+
+    let mut schedule = Map::new();
+    let identity = config.get("identity").and_then(Value::as_str);
+    if identity.is_none_or(|identity| identity == ledger.identity) {
+        let slots = (0..ledger.slots_per_epoch()).collect::<Vec<_>>();
+        schedule.insert(ledger.identity.clone(), slots.into());
+    }
+    Ok(schedule.into())
+}
+
+// The ledger's identity is the only validator, voting with its vote account
+// and the stake of every stake account.
+fn get_vote_accounts(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let config = config(params, 0);
+    let mut current = Vec::new();
+    let vote_pubkey = config.get("votePubkey").and_then(Value::as_str);
+    if vote_pubkey.is_none_or(|vote_pubkey| vote_pubkey == ledger.vote_account) {
+        let activated_stake: u64 = ledger
+            .accounts
+            .values()
+            .filter(|account| account.owner == STAKE_PROGRAM_ID)
+            .map(|account| account.lamports)
+            .sum();
+        current.push(json!({
+            "votePubkey": ledger.vote_account,
+            "nodePubkey": ledger.identity,
+            "activatedStake": activated_stake,
+            "epochVoteAccount": true,
+            "epochCredits": 0,
+            "commission": 0,
+            "lastVote": ledger.slot,
+            "rootSlot": ledger.slot.checked_sub(MAX_LOCKOUT_HISTORY),
+        }));
+    }
+    Ok(json!({ "current": current, "delinquent": [] }))
+}
+
+// Stake in the mock is always fully active.
+fn get_stake_activation(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let pubkey = string_param(params, 0)?;
+    check_min_context_slot(ledger, config(params, 1))?;
+    match ledger.accounts.get(pubkey) {
+        Some(account) if account.owner == STAKE_PROGRAM_ID => Ok(json!({
+            "state": "active",
+            "active": account.lamports,
+            "inactive": 0,
+        })),
+        Some(_) => Err(invalid_params("Invalid param: not a stake account")),
+        None => Err(invalid_params("Invalid param: account not found")),
+    }
+}
+
+fn inflation_rate(ledger: &Ledger) -> Value {
+    let years = ledger.slot as f64 / SLOTS_PER_YEAR as f64;
+    let total = (INFLATION_INITIAL * (1.0 - INFLATION_TAPER).powf(years)).max(INFLATION_TERMINAL);
+    let foundation = if years < INFLATION_FOUNDATION_TERM {
+        total * INFLATION_FOUNDATION
+    } else {
+        0.0
+    };
+    json!({
+        "total": total,
+        "validator": total - foundation,
+        "foundation": foundation,
+        "epoch": ledger.epoch(),
+    })
+}
+
+// The mock pays no inflation rewards.
+fn get_inflation_reward(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let addresses = params
+        .get(0)
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid_params("expected an array of addresses"))?;
+    check_min_context_slot(ledger, config(params, 1))?;
+    Ok(vec![Value::Null; addresses.len()].into())
+}
+
+// Every account in the mock is circulating.
+fn get_largest_accounts(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let mut accounts = match config(params, 0).get("filter").and_then(Value::as_str) {
+        Some("nonCirculating") => Vec::new(),
+        _ => ledger.accounts.iter().collect::<Vec<_>>(),
+    };
+    accounts.sort_by(|(a_pubkey, a), (b_pubkey, b)| b.lamports.cmp(&a.lamports).then(a_pubkey.cmp(b_pubkey)));
+    let accounts = accounts
+        .into_iter()
+        .take(MAX_LARGEST_ACCOUNTS)
+        .map(|(pubkey, account)| json!({ "address": pubkey, "lamports": account.lamports }))
+        .collect::<Vec<_>>();
+    Ok(with_context(ledger, accounts.into()))
+}
+
+fn get_supply(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let config = config(params, 0);
+    let total: u64 = ledger.accounts.values().map(|account| account.lamports).sum();
+    let mut supply = json!({
+        "total": total,
+        "circulating": total,
+        "nonCirculating": 0,
+    });
+    if !config
+        .get("excludeNonCirculatingAccountsList")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        supply["nonCirculatingAccounts"] = json!([]);
+    }
+    Ok(with_context(ledger, supply))
+}
+
+fn get_token_account_balance(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let pubkey = string_param(params, 0)?;
+    check_min_context_slot(ledger, config(params, 1))?;
+    let account = ledger
+        .accounts
+        .get(pubkey)
+        .and_then(TokenAccount::parse)
+        .ok_or_else(|| invalid_params("Invalid param: not a Token account"))?;
+    let mint = token_mint(ledger, &account.mint)?;
+    Ok(with_context(ledger, token_amount(account.amount, mint.decimals)))
+}
+
+// Accounts are matched by `owner_or_delegate`. The mint or program id
+// filter and the config may be separate parameters, as on the wire, or one
+// object.
+fn get_token_accounts(
+    ledger: &Ledger,
+    params: &Value,
+    owner_or_delegate: impl Fn(&TokenAccount) -> Option<&String>,
+) -> HandlerResult {
+    let pubkey = string_param(params, 0)?;
+    let filter = config(params, 1);
+    let config = match params.get(2) {
+        Some(_) => config(params, 2),
+        None => filter,
+    };
+    check_min_context_slot(ledger, config)?;
+
+    let mint = filter.get("mint").and_then(Value::as_str);
+    let program_id = filter.get("programId").and_then(Value::as_str);
+    if mint.is_none() && program_id.is_none() {
+        return Err(invalid_params("expected a mint or programId"));
+    }
+
+    let mut accounts = Vec::new();
+    for (address, account) in &ledger.accounts {
+        let Some(token_account) = TokenAccount::parse(account) else {
+            continue;
+        };
+        if owner_or_delegate(&token_account).map(String::as_str) != Some(pubkey)
+            || mint.is_some_and(|mint| mint != token_account.mint)
+            || program_id.is_some_and(|program_id| program_id != account.owner)
+        {
+            continue;
+        }
+        accounts.push(json!({
+            "pubkey": address,
+            "account": encode_account(account, config)?,
+        }));
+    }
+    Ok(with_context(ledger, accounts.into()))
+}
+
+fn get_token_largest_accounts(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let mint_pubkey = string_param(params, 0)?;
+    let mint = token_mint(ledger, mint_pubkey)?;
+
+    let mut accounts = ledger
+        .accounts
+        .iter()
+        .filter_map(|(address, account)| Some((address, TokenAccount::parse(account)?)))
+        .filter(|(_, account)| account.mint == mint_pubkey)
+        .collect::<Vec<_>>();
+    accounts.sort_by(|(a_address, a), (b_address, b)| b.amount.cmp(&a.amount).then(a_address.cmp(b_address)));
+    let accounts = accounts
+        .into_iter()
+        .take(MAX_LARGEST_ACCOUNTS)
+        .map(|(address, account)| {
+            let mut balance = token_amount(account.amount, mint.decimals);
+            balance["address"] = address.clone().into();
+            balance
+        })
+        .collect::<Vec<_>>();
+    Ok(with_context(ledger, accounts.into()))
+}
+
+fn get_token_supply(ledger: &Ledger, params: &Value) -> HandlerResult {
+    let mint_pubkey = string_param(params, 0)?;
+    check_min_context_slot(ledger, config(params, 1))?;
+    let mint = token_mint(ledger, mint_pubkey)?;
+    Ok(with_context(ledger, token_amount(mint.supply, mint.decimals)))
+}
+
+fn with_min_context_slot(
+    ledger: &Ledger,
+    params: &Value,
+    config_index: usize,
+    value: impl FnOnce(&Ledger) -> Value,
+) -> HandlerResult {
+    check_min_context_slot(ledger, config(params, config_index))?;
+    Ok(value(ledger))
+}
+
+fn with_context(ledger: &Ledger, value: Value) -> Value {
+    json!({
+        "context": { "slot": ledger.slot, "apiVersion": API_VERSION },
+        "value": value,
+    })
+}
+
+fn check_min_context_slot(ledger: &Ledger, config: &Map<String, Value>) -> Result<(), Fault> {
+    match config.get("minContextSlot").and_then(Value::as_u64) {
+        Some(min_context_slot) if min_context_slot > ledger.slot => Err(Fault::rpc(
+            MIN_CONTEXT_SLOT_NOT_REACHED,
+            "Minimum context slot has not been reached",
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn encode_account(account: &MockAccount, config: &Map<String, Value>) -> HandlerResult {
+    let encoding = config
+        .get("encoding")
+        .and_then(Value::as_str)
+        .unwrap_or("base58");
+
+    let mut data = account.data.as_slice();
+    if let Some(slice) = config.get("dataSlice") {
+        let offset = slice.get("offset").and_then(Value::as_u64).unwrap_or(0) as usize;
+        let length = slice.get("length").and_then(Value::as_u64).unwrap_or(0) as usize;
+        let start = offset.min(data.len());
+        let end = offset.saturating_add(length).min(data.len());
+        data = &data[start..end];
+    }
+
+    let data = match encoding {
+        "base58" => {
+            if data.len() > 128 {
+                return Err(Fault::rpc(
+                    INVALID_REQUEST,
+                    "Encoded binary (base 58) data should be less than 128 bytes, please use Base64 encoding.",
+                ));
+            }
+            json!([bs58::encode(data).into_string(), "base58"])
+        }
+        // The mock ships no account parsers, so like a real node it falls
+        // back to base64 for jsonParsed.
+        "base64" | "jsonParsed" => json!([BASE64_STANDARD.encode(data), "base64"]),
+        "base64+zstd" => {
+            let compressed = zstd::encode_all(data, 0)
+                .map_err(|err| Fault::rpc(INVALID_REQUEST, err.to_string()))?;
+            json!([BASE64_STANDARD.encode(compressed), "base64+zstd"])
+        }
+        other => return Err(invalid_params(&format!("unsupported encoding {}", other))),
+    };
+
+    Ok(json!({
+        "lamports": account.lamports,
+        "data": data,
+        "owner": account.owner,
+        "executable": account.executable,
+        "rentEpoch": account.rent_epoch,
+        "space": account.data.len(),
+    }))
+}
+
+fn matches_filters(account: &MockAccount, filters: &[Value]) -> Result<bool, Fault> {
+    for filter in filters {
+        if filter.as_str() == Some("tokenAccountState") {
+            if !is_initialized_token_account(&account.data) {
+                return Ok(false);
+            }
+        } else if let Some(size) = filter.get("dataSize").and_then(Value::as_u64) {
+            if account.data.len() as u64 != size {
+                return Ok(false);
+            }
+        } else if let Some(memcmp) = filter.get("memcmp") {
+            let offset = memcmp.get("offset").and_then(Value::as_u64).unwrap_or(0) as usize;
+            let encoded = memcmp
+                .get("bytes")
+                .and_then(Value::as_str)
+                .ok_or_else(|| invalid_params("memcmp filter is missing bytes"))?;
+            let bytes = match memcmp.get("encoding").and_then(Value::as_str) {
+                Some("base64") => BASE64_STANDARD
+                    .decode(encoded)
+                    .map_err(|_| invalid_params("invalid base64 memcmp bytes"))?,
+                _ => bs58::decode(encoded)
+                    .into_vec()
+                    .map_err(|_| invalid_params("invalid base58 memcmp bytes"))?,
+            };
+            if bytes.len() > 128 {
+                return Err(invalid_params("memcmp data exceeds 128 bytes"));
+            }
+            let end = offset
+                .checked_add(bytes.len())
+                .ok_or_else(|| invalid_params("memcmp offset is out of range"))?;
+            if account.data.get(offset..end) != Some(bytes.as_slice()) {
+                return Ok(false);
+            }
+        } else {
+            return Err(invalid_params("unsupported filter"));
+        }
+    }
+    Ok(true)
+}
+
+// A token account's state byte follows the mint, owner, amount and
+// delegate; Token-2022 accounts with extensions also carry an account type.
+fn is_initialized_token_account(data: &[u8]) -> bool {
+    const STATE_OFFSET: usize = 108;
+    const ACCOUNT_TYPE_OFFSET: usize = 165;
+    match data.len() {
+        165 => data[STATE_OFFSET] != 0,
+        len if len > ACCOUNT_TYPE_OFFSET => data[STATE_OFFSET] != 0 && data[ACCOUNT_TYPE_OFFSET] == 2,
+        _ => false,
+    }
+}
+
+fn landed_signatures(ledger: &Ledger, slot: u64) -> Vec<(&str, &MockSignatureStatus)> {
+    let mut signatures = ledger
+        .signatures
+        .iter()
+        .filter(|(_, status)| status.slot == slot)
+        .map(|(signature, status)| (signature.as_str(), status))
+        .collect::<Vec<_>>();
+    signatures.sort_by_key(|(signature, _)| *signature);
+    signatures
+}
+
+fn transaction_meta(status: &MockSignatureStatus) -> Value {
+    let status_result = match &status.err {
+        Some(err) => json!({ "Err": err }),
+        None => json!({ "Ok": null }),
+    };
+    json!({
+        "err": status.err,
+        "status": status_result,
+        "fee": LAMPORTS_PER_SIGNATURE,
+        "preBalances": [],
+        "postBalances": [],
+        "rewards": [],
+    })
+}
+
+fn token_amount(amount: u64, decimals: u8) -> Value {
+    let quantity = TokenQuantity::new(amount, decimals);
+    json!({
+        "amount": amount.to_string(),
+        "decimals": decimals,
+        "uiAmount": quantity.to_f64_lossy(),
+        "uiAmountString": quantity.to_string(),
+    })
+}
+
+fn is_token_program(program_id: &str) -> bool {
+    program_id == TOKEN_PROGRAM_ID || program_id == TOKEN_2022_PROGRAM_ID
+}
+
+// The fields of an initialized SPL Token account.
+struct TokenAccount {
+    mint: String,
+    owner: String,
+    amount: u64,
+    delegate: Option<String>,
+}
+
+impl TokenAccount {
+    fn parse(account: &MockAccount) -> Option<Self> {
+        if !is_token_program(&account.owner) || !is_initialized_token_account(&account.data) {
+            return None;
+        }
+        let data = &account.data;
+        let delegate = (data[72..76] != [0; 4]).then(|| bs58::encode(&data[76..108]).into_string());
+        Some(Self {
+            mint: bs58::encode(&data[..32]).into_string(),
+            owner: bs58::encode(&data[32..64]).into_string(),
+            amount: u64::from_le_bytes(data[64..72].try_into().unwrap()),
+            delegate,
+        })
+    }
+}
+
+struct TokenMint {
+    supply: u64,
+    decimals: u8,
+}
+
+// A mint is 82 bytes; Token-2022 mints with extensions are padded to the
+// length of a token account and then carry an account type.
+fn token_mint(ledger: &Ledger, pubkey: &str) -> Result<TokenMint, Fault> {
+    const MINT_LEN: usize = 82;
+    const ACCOUNT_TYPE_OFFSET: usize = 165;
+    let data = match ledger.accounts.get(pubkey) {
+        Some(account) if is_token_program(&account.owner) => account.data.as_slice(),
+        _ => return Err(invalid_params("Invalid param: could not find mint")),
+    };
+    let is_mint = data.len() == MINT_LEN || (data.len() > ACCOUNT_TYPE_OFFSET && data[ACCOUNT_TYPE_OFFSET] == 1);
+    if !is_mint || data[45] == 0 {
+        return Err(invalid_params("Invalid param: not a Token mint"));
+    }
+    Ok(TokenMint {
+        supply: u64::from_le_bytes(data[36..44].try_into().unwrap()),
+        decimals: data[44],
+    })
+}
+
+// The signature of a wire transaction is the first one after the
+// compact-u16 signature count.
+fn transaction_signature(params: &Value) -> Result<String, Fault> {
+    let encoded = string_param(params, 0)?;
+    let bytes = match config(params, 1).get("encoding").and_then(Value::as_str) {
+        Some("base64") => BASE64_STANDARD.decode(encoded).ok(),
+        _ => bs58::decode(encoded).into_vec().ok(),
+    };
+
+    match bytes.as_deref() {
+        Some([count, signature @ ..]) if *count > 0 && *count < 0x80 && signature.len() >= 64 => {
+            Ok(bs58::encode(&signature[..64]).into_string())
+        }
+        _ => Err(invalid_params("failed to deserialize transaction")),
+    }
+}
+
+fn string_param(params: &Value, index: usize) -> Result<&str, Fault> {
+    params
+        .get(index)
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid_params(&format!("expected a string at position {}", index)))
+}
+
+fn config(params: &Value, index: usize) -> &Map<String, Value> {
+    static EMPTY: std::sync::OnceLock<Map<String, Value>> = std::sync::OnceLock::new();
+    params
+        .get(index)
+        .and_then(Value::as_object)
+        .unwrap_or_else(|| EMPTY.get_or_init(Map::new))
+}
+
+fn invalid_params(message: &str) -> Fault {
+    Fault::rpc(INVALID_PARAMS, format!("Invalid params: {}", message))
+}
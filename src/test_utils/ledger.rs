@@ -0,0 +1,215 @@
+use std::collections::{HashMap, VecDeque};
+use std::num::NonZeroU64;
+
+/// Number of blocks a blockhash stays valid for.
+pub const MAX_PROCESSING_AGE: u64 = 150;
+
+/// Number of confirmations after which a slot is considered finalized.
+pub const MAX_LOCKOUT_HISTORY: u64 = 32;
+
+const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: String,
+    pub executable: bool,
+    pub rent_epoch: u64,
+}
+
+impl MockAccount {
+    /// A data-less account owned by the system program.
+    pub fn system(lamports: u64) -> Self {
+        Self {
+            lamports,
+            data: Vec::new(),
+            owner: SYSTEM_PROGRAM_ID.to_string(),
+            executable: false,
+            rent_epoch: u64::MAX,
+        }
+    }
+
+    pub fn with_data(mut self, owner: impl Into<String>, data: Vec<u8>) -> Self {
+        self.owner = owner.into();
+        self.data = data;
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockSignatureStatus {
+    pub slot: u64,
+    pub err: Option<serde_json::Value>,
+}
+
+/// In-memory state served by [`MockServer`](super::MockServer).
+///
+/// The ledger has a single fork: every slot produces a block, and every
+/// commitment level sees the same state.
+#[derive(Debug, Clone)]
+pub struct Ledger {
+    pub slot: u64,
+    pub block_height: u64,
+    pub transaction_count: u64,
+    pub genesis_hash: String,
+    pub identity: String,
+    pub vote_account: String,
+    pub accounts: HashMap<String, MockAccount>,
+    pub signatures: HashMap<String, MockSignatureStatus>,
+    slots_per_epoch: NonZeroU64,
+    blockhashes: VecDeque<(String, u64)>,
+}
+
+impl Default for Ledger {
+    fn default() -> Self {
+        let mut ledger = Self {
+            slot: 0,
+            block_height: 0,
+            transaction_count: 0,
+            genesis_hash: hash_for("genesis", 0),
+            identity: hash_for("identity", 0),
+            vote_account: hash_for("vote", 0),
+            accounts: HashMap::new(),
+            signatures: HashMap::new(),
+            slots_per_epoch: NonZeroU64::new(432_000).unwrap(),
+            blockhashes: VecDeque::new(),
+        };
+        ledger.push_blockhash();
+        ledger
+    }
+}
+
+impl Ledger {
+    pub fn with_slots_per_epoch(mut self, slots_per_epoch: NonZeroU64) -> Self {
+        self.slots_per_epoch = slots_per_epoch;
+        self
+    }
+
+    pub fn slots_per_epoch(&self) -> u64 {
+        self.slots_per_epoch.get()
+    }
+
+    pub fn set_account(&mut self, pubkey: impl Into<String>, account: MockAccount) {
+        self.accounts.insert(pubkey.into(), account);
+    }
+
+    pub fn remove_account(&mut self, pubkey: &str) -> Option<MockAccount> {
+        self.accounts.remove(pubkey)
+    }
+
+    /// Sets the balance of an account, creating a system account if needed.
+    pub fn set_balance(&mut self, pubkey: impl Into<String>, lamports: u64) {
+        self.accounts
+            .entry(pubkey.into())
+            .or_insert_with(|| MockAccount::system(0))
+            .lamports = lamports;
+    }
+
+    pub fn balance(&self, pubkey: &str) -> u64 {
+        self.accounts.get(pubkey).map_or(0, |account| account.lamports)
+    }
+
+    pub fn set_signature_status(&mut self, signature: impl Into<String>, status: MockSignatureStatus) {
+        self.signatures.insert(signature.into(), status);
+    }
+
+    /// Records a transaction as landed in the current slot.
+    pub fn land_transaction(&mut self, signature: impl Into<String>) {
+        self.transaction_count += 1;
+        self.signatures.insert(
+            signature.into(),
+            MockSignatureStatus {
+                slot: self.slot,
+                err: None,
+            },
+        );
+    }
+
+    /// Produces `slots` new blocks, each with a new blockhash.
+    pub fn advance_slots(&mut self, slots: u64) {
+        for _ in 0..slots {
+            self.slot += 1;
+            self.block_height += 1;
+            self.push_blockhash();
+        }
+    }
+
+    pub fn latest_blockhash(&self) -> &str {
+        &self.blockhashes.back().expect("ledger always has a blockhash").0
+    }
+
+    /// Last block height at which the latest blockhash can be used.
+    pub fn last_valid_block_height(&self) -> u64 {
+        self.block_height + MAX_PROCESSING_AGE
+    }
+
+    pub fn is_blockhash_valid(&self, blockhash: &str) -> bool {
+        self.blockhashes.iter().any(|(hash, _)| hash == blockhash)
+    }
+
+    /// The blockhash of the block at `slot`, or `None` if the slot has not
+    /// been produced yet.
+    pub fn blockhash(&self, slot: u64) -> Option<String> {
+        (slot <= self.slot).then(|| hash_for("blockhash", slot))
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.slot / self.slots_per_epoch
+    }
+
+    pub fn slot_index(&self) -> u64 {
+        self.slot % self.slots_per_epoch
+    }
+
+    pub(crate) fn signature_status(&self, signature: &str) -> Option<serde_json::Value> {
+        let status = self.signatures.get(signature)?;
+        let confirmations = self.slot.saturating_sub(status.slot);
+        let (confirmations, confirmation_status) = if confirmations >= MAX_LOCKOUT_HISTORY {
+            (serde_json::Value::Null, "finalized")
+        } else if confirmations >= 1 {
+            (confirmations.into(), "confirmed")
+        } else {
+            (0.into(), "processed")
+        };
+        let status_result = match &status.err {
+            Some(err) => serde_json::json!({ "Err": err }),
+            None => serde_json::json!({ "Ok": null }),
+        };
+
+        Some(serde_json::json!({
+            "slot": status.slot,
+            "confirmations": confirmations,
+            "err": status.err,
+            "status": status_result,
+            "confirmationStatus": confirmation_status,
+        }))
+    }
+
+    fn push_blockhash(&mut self) {
+        self.blockhashes
+            .push_back((hash_for("blockhash", self.slot), self.block_height));
+        while self.blockhashes.len() as u64 > MAX_PROCESSING_AGE {
+            self.blockhashes.pop_front();
+        }
+    }
+}
+
+// Deterministic, base58-encoded 32-byte value derived from a label and a seed.
+fn hash_for(label: &str, seed: u64) -> String {
+    let mut state = label
+        .bytes()
+        .fold(seed ^ 0x9e37_79b9_7f4a_7c15, |acc, byte| {
+            acc.rotate_left(5) ^ byte as u64
+        });
+    let mut bytes = [0u8; 32];
+    for chunk in bytes.chunks_mut(8) {
+        // splitmix64
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        chunk.copy_from_slice(&(z ^ (z >> 31)).to_le_bytes());
+    }
+    bs58::encode(bytes).into_string()
+}
@@ -0,0 +1,301 @@
+//! In-process mock Solana JSON-RPC server for tests.
+//!
+//! [`MockServer`] serves the methods in [`crate::methods`] from an in-memory
+//! [`Ledger`], and can inject faults, latency and custom responses.
+//!
+//! The ledger has a single validator, its identity, which leads every slot
+//! and holds every stake account's stake. It keeps signatures rather than
+//! transactions, so blocks and `getTransaction` carry only each
+//! transaction's signature, with a fee of 5000 lamports of which half is
+//! burned. Token methods read SPL Token and Token-2022 account data set with
+//! [`Ledger::set_account`].
+
+mod handlers;
+mod ledger;
+
+pub use ledger::{Ledger, MockAccount, MockSignatureStatus, MAX_LOCKOUT_HISTORY, MAX_PROCESSING_AGE};
+
+use crate::client::JsonRpcClient;
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const PARSE_ERROR: i64 = -32700;
+
+/// A failure returned by the mock instead of a result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// A JSON-RPC error response.
+    Rpc { code: i64, message: String },
+    /// A bare HTTP error status, e.g. `429` to simulate rate limiting.
+    Http(u16),
+}
+
+impl Fault {
+    pub fn rpc(code: i64, message: impl Into<String>) -> Self {
+        Fault::Rpc {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+type Handler = Arc<dyn Fn(&Value) -> Result<Value, Fault> + Send + Sync>;
+
+/// A call received by the mock.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReceivedCall {
+    pub method: String,
+    pub params: Value,
+}
+
+#[derive(Default)]
+struct State {
+    ledger: Mutex<Ledger>,
+    hooks: Mutex<Hooks>,
+    calls: Mutex<Vec<ReceivedCall>>,
+}
+
+#[derive(Default)]
+struct Hooks {
+    latency: Option<Duration>,
+    method_latency: HashMap<String, Duration>,
+    // `None` repeats the fault forever, `Some(n)` for `n` more calls.
+    faults: HashMap<String, (Fault, Option<usize>)>,
+    handlers: HashMap<String, Handler>,
+}
+
+/// Local HTTP JSON-RPC server backed by an in-memory [`Ledger`].
+///
+/// The server listens on an ephemeral port on `127.0.0.1` and stops when
+/// dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<State>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    pub async fn start() -> Self {
+        Self::with_ledger(Ledger::default()).await
+    }
+
+    pub async fn with_ledger(ledger: Ledger) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind mock server");
+        let addr = listener.local_addr().expect("mock server has no local address");
+
+        let state = Arc::new(State {
+            ledger: Mutex::new(ledger),
+            ..State::default()
+        });
+
+        let task = tokio::spawn(accept(listener, state.clone()));
+        Self { addr, state, task }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A client connected to this server.
+    pub fn client(&self) -> JsonRpcClient {
+        JsonRpcClient::connect(self.url())
+    }
+
+    pub fn ledger(&self) -> MutexGuard<'_, Ledger> {
+        self.state.ledger.lock().unwrap()
+    }
+
+    /// Every call received so far, in order.
+    pub fn calls(&self) -> Vec<ReceivedCall> {
+        self.state.calls.lock().unwrap().clone()
+    }
+
+    pub fn call_count(&self, method: &str) -> usize {
+        self.state
+            .calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|call| call.method == method)
+            .count()
+    }
+
+    /// Delays every response by `latency`.
+    pub fn set_latency(&self, latency: Duration) {
+        self.hooks().latency = Some(latency);
+    }
+
+    /// Delays responses to `method` by `latency`, overriding
+    /// [`MockServer::set_latency`].
+    pub fn set_method_latency(&self, method: impl Into<String>, latency: Duration) {
+        self.hooks().method_latency.insert(method.into(), latency);
+    }
+
+    /// Fails every call to `method` with `fault` until cleared.
+    pub fn inject_fault(&self, method: impl Into<String>, fault: Fault) {
+        self.hooks().faults.insert(method.into(), (fault, None));
+    }
+
+    /// Fails the next `times` calls to `method` with `fault`. Replaces any
+    /// fault already injected for `method`; `0` just clears it.
+    pub fn inject_fault_times(&self, method: impl Into<String>, fault: Fault, times: usize) {
+        let method = method.into();
+        let mut hooks = self.hooks();
+        if times == 0 {
+            hooks.faults.remove(&method);
+        } else {
+            hooks.faults.insert(method, (fault, Some(times)));
+        }
+    }
+
+    /// Answers `method` with `handler` instead of the ledger. Also used to
+    /// add methods the mock does not implement.
+    pub fn respond_with<F>(&self, method: impl Into<String>, handler: F)
+    where
+        F: Fn(&Value) -> Result<Value, Fault> + Send + Sync + 'static,
+    {
+        self.hooks().handlers.insert(method.into(), Arc::new(handler));
+    }
+
+    /// Removes all latency, faults and custom handlers.
+    pub fn reset_hooks(&self) {
+        *self.hooks() = Hooks::default();
+    }
+
+    fn hooks(&self) -> MutexGuard<'_, Hooks> {
+        self.state.hooks.lock().unwrap()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl std::fmt::Debug for MockServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockServer").field("addr", &self.addr).finish_non_exhaustive()
+    }
+}
+
+async fn accept(listener: TcpListener, state: Arc<State>) {
+    while let Ok((stream, _)) = listener.accept().await {
+        let state = state.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| serve(state.clone(), request));
+            let _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+async fn serve(state: Arc<State>, request: Request<Incoming>) -> Result<Response<Full<Bytes>>, hyper::Error> {
+    let body = request.into_body().collect().await?.to_bytes();
+
+    let response = match serde_json::from_slice::<Value>(&body) {
+        Ok(Value::Array(batch)) => {
+            let mut responses = Vec::with_capacity(batch.len());
+            for request in &batch {
+                match dispatch(&state, request).await {
+                    Ok(response) => responses.push(response),
+                    Err(status) => return Ok(status_response(status)),
+                }
+            }
+            Value::Array(responses)
+        }
+        Ok(request) => match dispatch(&state, &request).await {
+            Ok(response) => response,
+            Err(status) => return Ok(status_response(status)),
+        },
+        Err(_) => error_response(Value::Null, PARSE_ERROR, "Parse error"),
+    };
+
+    Ok(Response::builder()
+        .header("content-type", "application/json")
+        .body(Full::new(Bytes::from(response.to_string())))
+        .unwrap())
+}
+
+// Returns the JSON-RPC response for one request, or the HTTP status to fail
+// the whole exchange with.
+async fn dispatch(state: &State, request: &Value) -> Result<Value, StatusCode> {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request.get("method").and_then(Value::as_str).unwrap_or_default();
+    let params = request.get("params").cloned().unwrap_or(Value::Array(Vec::new()));
+
+    state.calls.lock().unwrap().push(ReceivedCall {
+        method: method.to_string(),
+        params: params.clone(),
+    });
+
+    let (latency, fault, handler) = {
+        let mut hooks = state.hooks.lock().unwrap();
+        let latency = hooks.method_latency.get(method).copied().or(hooks.latency);
+        let fault = take_fault(&mut hooks, method);
+        let handler = hooks.handlers.get(method).cloned();
+        (latency, fault, handler)
+    };
+
+    if let Some(latency) = latency {
+        tokio::time::sleep(latency).await;
+    }
+
+    let result = match (fault, handler) {
+        (Some(fault), _) => Err(fault),
+        (None, Some(handler)) => handler(&params),
+        (None, None) => {
+            let mut ledger = state.ledger.lock().unwrap();
+            handlers::handle(&mut ledger, method, &params)
+                .unwrap_or_else(|| Err(Fault::rpc(METHOD_NOT_FOUND, "Method not found")))
+        }
+    };
+
+    match result {
+        Ok(result) => Ok(json!({ "jsonrpc": "2.0", "result": result, "id": id })),
+        Err(Fault::Rpc { code, message }) => Ok(error_response(id, code, &message)),
+        Err(Fault::Http(status)) => Err(StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)),
+    }
+}
+
+fn take_fault(hooks: &mut Hooks, method: &str) -> Option<Fault> {
+    let (fault, remaining) = hooks.faults.get_mut(method)?;
+    let fault = fault.clone();
+    if let Some(remaining) = remaining {
+        *remaining = remaining.saturating_sub(1);
+        if *remaining == 0 {
+            hooks.faults.remove(method);
+        }
+    }
+    Some(fault)
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": { "code": code, "message": message },
+        "id": id,
+    })
+}
+
+fn status_response(status: StatusCode) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .body(Full::new(Bytes::new()))
+        .unwrap()
+}
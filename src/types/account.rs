@@ -1,17 +1,26 @@
+use super::parsed::ParsedAccount;
+use super::Lamports;
+use base64::prelude::{Engine, BASE64_STANDARD};
 use serde::{Deserialize, Serialize};
+use std::io::Read;
+use thiserror::Error;
+
+/// The most data an account can hold. Decompressed `base64+zstd` data is
+/// not allowed to grow past it.
+pub const MAX_ACCOUNT_DATA_LEN: u64 = 10 * 1024 * 1024;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Account {
-    pub lamports: u64,
+    pub lamports: Lamports,
     pub data: Vec<u8>,
     pub owner: String,
     pub executable: bool,
     pub rent_epoch: u64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AccountInfo {
-    pub lamports: u64,
+    pub lamports: Lamports,
     #[serde(rename = "data")]
     pub data_encoded: AccountData,
     pub owner: String,
@@ -22,7 +31,7 @@ pub struct AccountInfo {
     pub space: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum AccountData {
     // Array format: [data_string, encoding]
@@ -33,3 +42,225 @@ pub enum AccountData {
     Object(serde_json::Value),
 }
 
+#[derive(Error, Debug)]
+pub enum AccountDataError {
+    #[error("Account data is not binary-encoded")]
+    NotBinary,
+
+    #[error("Unsupported account data encoding: {0}")]
+    UnsupportedEncoding(String),
+
+    #[error("Invalid base58 account data: {0}")]
+    Base58(#[from] bs58::decode::Error),
+
+    #[error("Invalid base64 account data: {0}")]
+    Base64(#[from] base64::DecodeError),
+
+    #[error("Invalid zstd account data: {0}")]
+    Zstd(#[from] std::io::Error),
+
+    #[error("Decompressed account data exceeds {MAX_ACCOUNT_DATA_LEN} bytes")]
+    TooLarge,
+
+    #[error("Account data is not jsonParsed")]
+    NotParsed,
+
+    #[error("Account is not a token account")]
+    NotTokenAccount,
+
+    #[error("Unrecognized jsonParsed account data: {0}")]
+    InvalidParsed(#[from] serde_json::Error),
+
+    #[error("Account data is truncated to {actual} of {space} bytes, likely by a data_slice")]
+    Truncated { actual: usize, space: u64 },
+}
+
+impl AccountData {
+    /// Decodes binary account data into raw bytes, decompressing
+    /// `base64+zstd` data. A bare string is legacy `binary` data, which is
+    /// base58.
+    pub fn decode(&self) -> Result<Vec<u8>, AccountDataError> {
+        let (data, encoding) = match self {
+            AccountData::Array(parts) => match parts.as_slice() {
+                [serde_json::Value::String(data), serde_json::Value::String(encoding)] => {
+                    (data.as_str(), encoding.as_str())
+                }
+                _ => return Err(AccountDataError::NotBinary),
+            },
+            AccountData::String(data) => (data.as_str(), "base58"),
+            AccountData::Object(_) => return Err(AccountDataError::NotBinary),
+        };
+
+        match encoding {
+            "base58" => Ok(bs58::decode(data).into_vec()?),
+            "base64" => Ok(BASE64_STANDARD.decode(data)?),
+            "base64+zstd" => {
+                let compressed = BASE64_STANDARD.decode(data)?;
+                let mut bytes = Vec::new();
+                zstd::Decoder::new(compressed.as_slice())?
+                    .take(MAX_ACCOUNT_DATA_LEN + 1)
+                    .read_to_end(&mut bytes)?;
+                if bytes.len() as u64 > MAX_ACCOUNT_DATA_LEN {
+                    return Err(AccountDataError::TooLarge);
+                }
+                Ok(bytes)
+            }
+            other => Err(AccountDataError::UnsupportedEncoding(other.to_string())),
+        }
+    }
+
+    /// The `jsonParsed` form of the data, when the node had a parser for the
+    /// owning program.
+    pub fn parsed(&self) -> Option<&serde_json::Value> {
+        match self {
+            AccountData::Object(parsed) => Some(parsed),
+            _ => None,
+        }
+    }
+
+    /// Decodes `jsonParsed` data into a [`ParsedAccount`].
+    pub fn parsed_account(&self) -> Result<ParsedAccount, AccountDataError> {
+        let parsed = self.parsed().ok_or(AccountDataError::NotParsed)?;
+        Ok(ParsedAccount::deserialize(parsed)?)
+    }
+}
+
+impl AccountInfo {
+    /// Decodes the account data into bytes.
+    ///
+    /// Fails with [`AccountDataError::Truncated`] when the node reports more
+    /// data than it returned, which happens when a `data_slice` was
+    /// requested. Use [`AccountData::decode`] to read a slice on purpose.
+    pub fn data_bytes(&self) -> Result<Vec<u8>, AccountDataError> {
+        let bytes = self.data_encoded.decode()?;
+        match self.space {
+            Some(space) if (bytes.len() as u64) < space => Err(AccountDataError::Truncated {
+                actual: bytes.len(),
+                space,
+            }),
+            _ => Ok(bytes),
+        }
+    }
+
+    pub fn parsed(&self) -> Option<&serde_json::Value> {
+        self.data_encoded.parsed()
+    }
+
+    pub fn parsed_account(&self) -> Result<ParsedAccount, AccountDataError> {
+        self.data_encoded.parsed_account()
+    }
+}
+
+impl TryFrom<AccountInfo> for Account {
+    type Error = AccountDataError;
+
+    fn try_from(info: AccountInfo) -> Result<Self, Self::Error> {
+        Ok(Account {
+            data: info.data_bytes()?,
+            lamports: info.lamports,
+            owner: info.owner,
+            executable: info.executable,
+            rent_epoch: info.rent_epoch,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn binary(data: String, encoding: &str) -> AccountData {
+        AccountData::Array(vec![json!(data), json!(encoding)])
+    }
+
+    #[test]
+    fn decodes_base64_zstd() {
+        let bytes: Vec<u8> = (0..=255).cycle().take(4096).collect();
+        let compressed = zstd::encode_all(bytes.as_slice(), 0).unwrap();
+        let data = binary(BASE64_STANDARD.encode(compressed), "base64+zstd");
+        assert_eq!(data.decode().unwrap(), bytes);
+    }
+
+    #[test]
+    fn decodes_zstd_up_to_the_account_size_limit() {
+        let bytes = vec![0u8; MAX_ACCOUNT_DATA_LEN as usize];
+        let compressed = zstd::encode_all(bytes.as_slice(), 0).unwrap();
+        let data = binary(BASE64_STANDARD.encode(compressed), "base64+zstd");
+        assert_eq!(data.decode().unwrap().len(), bytes.len());
+    }
+
+    #[test]
+    fn rejects_corrupt_zstd() {
+        let data = binary(BASE64_STANDARD.encode(b"not zstd"), "base64+zstd");
+        assert!(matches!(data.decode(), Err(AccountDataError::Zstd(_))));
+    }
+
+    #[test]
+    fn rejects_unknown_encodings() {
+        let data = binary("AAAA".to_string(), "base32");
+        assert!(matches!(data.decode(), Err(AccountDataError::UnsupportedEncoding(e)) if e == "base32"));
+    }
+
+    #[test]
+    fn decodes_base58_and_base64() {
+        let bytes = vec![0, 1, 2, 254, 255];
+        assert_eq!(binary(bs58::encode(&bytes).into_string(), "base58").decode().unwrap(), bytes);
+        assert_eq!(binary(BASE64_STANDARD.encode(&bytes), "base64").decode().unwrap(), bytes);
+        assert_eq!(AccountData::String(bs58::encode(&bytes).into_string()).decode().unwrap(), bytes);
+    }
+
+    #[test]
+    fn parsed_data_is_not_binary() {
+        let data = AccountData::Object(json!({ "program": "nonce", "parsed": {}, "space": 80 }));
+        assert!(matches!(data.decode(), Err(AccountDataError::NotBinary)));
+        assert!(data.parsed().is_some());
+        assert!(binary(String::new(), "base64").parsed().is_none());
+        assert!(matches!(
+            binary(String::new(), "base64").parsed_account(),
+            Err(AccountDataError::NotParsed)
+        ));
+    }
+
+    #[test]
+    fn deserializes_every_data_form() {
+        let info = |data| {
+            serde_json::from_value::<AccountInfo>(json!({
+                "lamports": 1,
+                "data": data,
+                "owner": "11111111111111111111111111111111",
+                "executable": false,
+                "rentEpoch": 0,
+            }))
+            .unwrap()
+        };
+        assert!(matches!(info(json!(["AQID", "base64"])).data_encoded, AccountData::Array(_)));
+        assert!(matches!(info(json!("Ldp")).data_encoded, AccountData::String(_)));
+        assert!(matches!(info(json!({ "program": "nonce" })).data_encoded, AccountData::Object(_)));
+    }
+
+    #[test]
+    fn sliced_data_is_truncated() {
+        let info = AccountInfo {
+            lamports: Lamports(1),
+            data_encoded: binary(BASE64_STANDARD.encode([1, 2]), "base64"),
+            owner: "11111111111111111111111111111111".to_string(),
+            executable: false,
+            rent_epoch: 0,
+            space: Some(4),
+        };
+        assert!(matches!(
+            info.data_bytes(),
+            Err(AccountDataError::Truncated { actual: 2, space: 4 })
+        ));
+        assert_eq!(info.data_encoded.decode().unwrap(), vec![1, 2]);
+
+        let whole = AccountInfo {
+            space: Some(2),
+            ..info.clone()
+        };
+        let account = Account::try_from(whole).unwrap();
+        assert_eq!(account.data, vec![1, 2]);
+        assert!(Account::try_from(info).is_err());
+    }
+}
@@ -0,0 +1,363 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+const SOL_DECIMALS: u8 = 9;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AmountError {
+    #[error("Invalid amount {0:?}")]
+    Invalid(String),
+
+    #[error("{input:?} has more than {decimals} decimal places")]
+    TooPrecise { input: String, decimals: u8 },
+
+    #[error("{0:?} is out of range")]
+    Overflow(String),
+}
+
+/// An amount of lamports.
+///
+/// Formats as SOL (`"1.5 SOL"`), parses either SOL with its unit or a bare
+/// number of lamports, and only offers checked arithmetic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Lamports(pub u64);
+
+impl Lamports {
+    pub const ZERO: Lamports = Lamports(0);
+
+    pub const fn new(lamports: u64) -> Self {
+        Self(lamports)
+    }
+
+    /// `sol` whole SOL, or `None` if that overflows.
+    pub const fn from_sol(sol: u64) -> Option<Self> {
+        match sol.checked_mul(LAMPORTS_PER_SOL) {
+            Some(lamports) => Some(Self(lamports)),
+            None => None,
+        }
+    }
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// The amount in SOL without trailing zeros, e.g. `"1.5"`.
+    pub fn to_sol_string(self) -> String {
+        format_decimal(self.0 as u128, SOL_DECIMALS)
+    }
+
+    pub fn checked_add(self, other: Lamports) -> Option<Lamports> {
+        self.0.checked_add(other.0).map(Lamports)
+    }
+
+    pub fn checked_sub(self, other: Lamports) -> Option<Lamports> {
+        self.0.checked_sub(other.0).map(Lamports)
+    }
+
+    pub fn checked_mul(self, factor: u64) -> Option<Lamports> {
+        self.0.checked_mul(factor).map(Lamports)
+    }
+
+    pub fn checked_div(self, divisor: u64) -> Option<Lamports> {
+        self.0.checked_div(divisor).map(Lamports)
+    }
+
+    pub fn saturating_add(self, other: Lamports) -> Lamports {
+        Lamports(self.0.saturating_add(other.0))
+    }
+
+    pub fn saturating_sub(self, other: Lamports) -> Lamports {
+        Lamports(self.0.saturating_sub(other.0))
+    }
+
+    /// Sums `amounts`, or returns `None` on overflow.
+    pub fn checked_sum(amounts: impl IntoIterator<Item = Lamports>) -> Option<Lamports> {
+        amounts.into_iter().try_fold(Lamports::ZERO, Lamports::checked_add)
+    }
+}
+
+impl From<u64> for Lamports {
+    fn from(lamports: u64) -> Self {
+        Self(lamports)
+    }
+}
+
+impl From<Lamports> for u64 {
+    fn from(lamports: Lamports) -> Self {
+        lamports.0
+    }
+}
+
+impl fmt::Display for Lamports {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} SOL", self.to_sol_string())
+    }
+}
+
+/// Parses either an amount of SOL with its unit, such as `"0.000000001 SOL"`,
+/// or a bare integer number of lamports such as `"5000"`. A bare decimal like
+/// `"1.5"` is rejected rather than guessed at, and more than nine decimal
+/// places of SOL is an error rather than being rounded.
+impl FromStr for Lamports {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(sol) = s.strip_suffix(" SOL") else {
+            if s.is_empty() || !s.bytes().all(|byte| byte.is_ascii_digit()) {
+                return Err(AmountError::Invalid(s.to_string()));
+            }
+            return s.parse().map(Lamports).map_err(|_| AmountError::Overflow(s.to_string()));
+        };
+        u64::try_from(parse_decimal(sol, SOL_DECIMALS)?)
+            .map(Lamports)
+            .map_err(|_| AmountError::Overflow(s.to_string()))
+    }
+}
+
+/// A token amount in base units together with the mint's decimals.
+///
+/// Formatting and parsing are exact. Converting to a float has to be asked
+/// for with [`TokenQuantity::to_f64_lossy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenQuantity {
+    amount: u128,
+    decimals: u8,
+}
+
+impl TokenQuantity {
+    pub fn new(amount: impl Into<u128>, decimals: u8) -> Self {
+        Self {
+            amount: amount.into(),
+            decimals,
+        }
+    }
+
+    /// Parses a UI amount such as `"12.5"` for a mint with `decimals`.
+    pub fn parse(s: &str, decimals: u8) -> Result<Self, AmountError> {
+        Ok(Self::new(parse_decimal(s, decimals)?, decimals))
+    }
+
+    /// Parses an amount in base units, as the RPC's `amount` fields are.
+    pub fn from_raw(amount: &str, decimals: u8) -> Result<Self, AmountError> {
+        if amount.is_empty() || !amount.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(AmountError::Invalid(amount.to_string()));
+        }
+        let amount: u128 = amount
+            .parse()
+            .map_err(|_| AmountError::Overflow(amount.to_string()))?;
+        Ok(Self::new(amount, decimals))
+    }
+
+    /// The amount in base units.
+    pub fn amount(&self) -> u128 {
+        self.amount
+    }
+
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// The amount in base units, if it fits in a `u64` as SPL Token amounts
+    /// do.
+    pub fn to_u64(&self) -> Option<u64> {
+        u64::try_from(self.amount).ok()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.amount == 0
+    }
+
+    /// Adds two quantities of the same mint. `None` if the decimals differ
+    /// or the sum overflows.
+    pub fn checked_add(self, other: TokenQuantity) -> Option<TokenQuantity> {
+        if self.decimals != other.decimals {
+            return None;
+        }
+        self.amount
+            .checked_add(other.amount)
+            .map(|amount| Self::new(amount, self.decimals))
+    }
+
+    /// Subtracts two quantities of the same mint. `None` if the decimals
+    /// differ or the result would be negative.
+    pub fn checked_sub(self, other: TokenQuantity) -> Option<TokenQuantity> {
+        if self.decimals != other.decimals {
+            return None;
+        }
+        self.amount
+            .checked_sub(other.amount)
+            .map(|amount| Self::new(amount, self.decimals))
+    }
+
+    /// The nearest `f64`, for display or statistics only.
+    ///
+    /// This is what the RPC's `uiAmount` fields hold. Above 2^53 base units,
+    /// or with enough decimals, neighbouring amounts round to the same float,
+    /// so comparing or adding them gives wrong answers.
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.amount as f64 / 10f64.powi(self.decimals as i32)
+    }
+}
+
+/// Quantities with different decimals are not comparable.
+impl PartialOrd for TokenQuantity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.decimals == other.decimals).then(|| self.amount.cmp(&other.amount))
+    }
+}
+
+/// Formats like the RPC's `uiAmountString`: exact, without trailing zeros.
+impl fmt::Display for TokenQuantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_decimal(self.amount, self.decimals))
+    }
+}
+
+fn format_decimal(amount: u128, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return amount.to_string();
+    }
+
+    let digits = format!("{:0>width$}", amount, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+fn parse_decimal(s: &str, decimals: u8) -> Result<u128, AmountError> {
+    let (integer, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(integer) || !is_digits(fraction) {
+        return Err(AmountError::Invalid(s.to_string()));
+    }
+    if fraction.len() > decimals as usize {
+        return Err(AmountError::TooPrecise {
+            input: s.to_string(),
+            decimals,
+        });
+    }
+
+    let overflow = || AmountError::Overflow(s.to_string());
+    let scale = 10u128.checked_pow(decimals as u32).ok_or_else(overflow)?;
+    let integer: u128 = match integer {
+        "" => 0,
+        integer => integer.parse().map_err(|_| overflow())?,
+    };
+    let fraction: u128 = match fraction {
+        "" => 0,
+        fraction => fraction.parse::<u128>().map_err(|_| overflow())? * 10u128.pow((decimals as usize - fraction.len()) as u32),
+    };
+    integer
+        .checked_mul(scale)
+        .and_then(|amount| amount.checked_add(fraction))
+        .ok_or_else(overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_without_trailing_zeros() {
+        assert_eq!(format_decimal(0, 9), "0");
+        assert_eq!(format_decimal(1, 9), "0.000000001");
+        assert_eq!(format_decimal(1_500_000_000, 9), "1.5");
+        assert_eq!(format_decimal(2_000_000_000, 9), "2");
+        assert_eq!(format_decimal(42, 0), "42");
+        assert_eq!(format_decimal(u128::MAX, 38), "3.40282366920938463463374607431768211455");
+    }
+
+    #[test]
+    fn parses_exact_decimals() {
+        assert_eq!(parse_decimal("1.5", 9), Ok(1_500_000_000));
+        assert_eq!(parse_decimal(".5", 2), Ok(50));
+        assert_eq!(parse_decimal("7.", 2), Ok(700));
+        assert_eq!(parse_decimal("0.000000001", 9), Ok(1));
+        assert_eq!(parse_decimal("12", 0), Ok(12));
+    }
+
+    #[test]
+    fn rejects_malformed_or_imprecise_decimals() {
+        for input in ["", ".", "-1", "1.2.3", "1e9", " 1", "1,5", "+1"] {
+            assert_eq!(parse_decimal(input, 9), Err(AmountError::Invalid(input.to_string())), "{:?}", input);
+        }
+        assert_eq!(
+            parse_decimal("0.0000000001", 9),
+            Err(AmountError::TooPrecise {
+                input: "0.0000000001".to_string(),
+                decimals: 9
+            })
+        );
+        assert!(matches!(parse_decimal("1", 39), Err(AmountError::Overflow(_))));
+        assert!(matches!(
+            parse_decimal("340282366920938463463374607431768211456", 0),
+            Err(AmountError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn lamports_round_trip_through_sol() {
+        let lamports = Lamports(1_500_000_001);
+        assert_eq!(lamports.to_string(), "1.500000001 SOL");
+        assert_eq!(lamports.to_string().parse::<Lamports>(), Ok(lamports));
+        assert_eq!("2 SOL".parse::<Lamports>(), Ok(Lamports(2 * LAMPORTS_PER_SOL)));
+        assert!(matches!("18446744074 SOL".parse::<Lamports>(), Err(AmountError::Overflow(_))));
+        assert_eq!(Lamports::from_sol(u64::MAX), None);
+    }
+
+    #[test]
+    fn bare_numbers_parse_as_lamports() {
+        assert_eq!("5000".parse::<Lamports>(), Ok(Lamports(5000)));
+        assert_eq!("18446744073709551615".parse::<Lamports>(), Ok(Lamports(u64::MAX)));
+        assert!(matches!("18446744073709551616".parse::<Lamports>(), Err(AmountError::Overflow(_))));
+        for input in ["1.5", "7.", "", "-1", "1 lamports"] {
+            assert_eq!(input.parse::<Lamports>(), Err(AmountError::Invalid(input.to_string())), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn lamports_arithmetic_is_checked() {
+        assert_eq!(Lamports(u64::MAX).checked_add(Lamports(1)), None);
+        assert_eq!(Lamports(1).checked_sub(Lamports(2)), None);
+        assert_eq!(Lamports(1).saturating_sub(Lamports(2)), Lamports::ZERO);
+        assert_eq!(Lamports(10).checked_div(0), None);
+        assert_eq!(Lamports::checked_sum([Lamports(1), Lamports(2)]), Some(Lamports(3)));
+        assert_eq!(Lamports::checked_sum([Lamports(u64::MAX), Lamports(1)]), None);
+    }
+
+    #[test]
+    fn token_quantities_are_exact() {
+        let quantity = TokenQuantity::from_raw("123456789", 6).unwrap();
+        assert_eq!(quantity.to_string(), "123.456789");
+        assert_eq!(TokenQuantity::parse("123.456789", 6), Ok(quantity));
+        assert_eq!(quantity.to_u64(), Some(123_456_789));
+
+        let huge = TokenQuantity::new(u128::from(u64::MAX) + 1, 0);
+        assert_eq!(huge.to_u64(), None);
+
+        assert!(matches!(TokenQuantity::from_raw("", 6), Err(AmountError::Invalid(_))));
+        assert!(matches!(TokenQuantity::from_raw("1.5", 6), Err(AmountError::Invalid(_))));
+    }
+
+    #[test]
+    fn token_quantities_of_different_mints_do_not_mix() {
+        let a = TokenQuantity::new(5u64, 2);
+        let b = TokenQuantity::new(5u64, 3);
+        assert_eq!(a.checked_add(b), None);
+        assert_eq!(a.checked_sub(b), None);
+        assert_eq!(a.partial_cmp(&b), None);
+        assert_eq!(a.checked_add(a), Some(TokenQuantity::new(10u64, 2)));
+        assert!(a < TokenQuantity::new(6u64, 2));
+    }
+}
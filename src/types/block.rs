@@ -1,4 +1,7 @@
+use super::reward::{rewards_by_account, total_rewards};
+use super::{BlockTransaction, Hash, Lamports, Reward, RewardType};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -10,14 +13,138 @@ pub enum BlockEncoding {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Block {
-    pub blockhash: Option<String>,
-    pub previous_blockhash: Option<String>,
+    pub blockhash: Option<Hash>,
+    pub previous_blockhash: Option<Hash>,
     pub parent_slot: Option<u64>,
-    pub transactions: Option<Vec<serde_json::Value>>,
-    pub rewards: Option<Vec<serde_json::Value>>,
+    pub transactions: Option<Vec<BlockTransaction>>,
+    pub rewards: Option<Vec<Reward>>,
     pub block_time: Option<i64>,
     pub block_height: Option<u64>,
 }
 
+impl Block {
+    /// Fees paid by the block's transactions. `None` unless the block was
+    /// fetched with its transactions and their metadata.
+    pub fn total_fees(&self) -> Option<Lamports> {
+        self.transactions
+            .as_ref()?
+            .iter()
+            .map(|transaction| transaction.meta.as_ref().map(|meta| meta.fee))
+            .try_fold(Lamports::ZERO, |total, fee| total.checked_add(fee?))
+    }
+
+    /// Fees paid to the slot leader. `None` unless the block was fetched
+    /// with rewards.
+    pub fn leader_fees(&self) -> Option<Lamports> {
+        Some(total_rewards(self.rewards.as_ref()?, RewardType::Fee))
+    }
+
+    /// Fees burned rather than paid to the slot leader. Needs both
+    /// transactions and rewards.
+    pub fn burned_fees(&self) -> Option<Lamports> {
+        self.total_fees()?.checked_sub(self.leader_fees()?)
+    }
+
+    /// Inflation rewards per stake account. Stake rewards are paid in the
+    /// first blocks of an epoch; the vote account a stake account delegates
+    /// to is in its account data, not in the reward.
+    pub fn staking_rewards(&self) -> HashMap<String, Lamports> {
+        rewards_by_account(self.rewards.iter().flatten(), RewardType::Staking)
+    }
+
+    /// Inflation rewards summed per vote account, given the vote account
+    /// each stake account delegates to. Rewards of stake accounts missing
+    /// from `delegations` are left out.
+    pub fn staking_rewards_by_vote_account(&self, delegations: &HashMap<String, String>) -> HashMap<String, Lamports> {
+        let mut totals: HashMap<String, Lamports> = HashMap::new();
+        for (stake_account, credit) in self.staking_rewards() {
+            if let Some(vote_account) = delegations.get(&stake_account) {
+                let total = totals.entry(vote_account.clone()).or_default();
+                *total = total.saturating_add(credit);
+            }
+        }
+        totals
+    }
+
+    /// Commission per vote account, paid alongside staking rewards.
+    pub fn voting_rewards(&self) -> HashMap<String, Lamports> {
+        rewards_by_account(self.rewards.iter().flatten(), RewardType::Voting)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn block(fees: &[u64], rewards: serde_json::Value) -> Block {
+        let transactions: Vec<_> = fees
+            .iter()
+            .map(|fee| json!({ "transaction": [], "meta": { "err": null, "fee": fee, "preBalances": [], "postBalances": [] } }))
+            .collect();
+        serde_json::from_value(json!({
+            "blockhash": null,
+            "previousBlockhash": null,
+            "parentSlot": 9,
+            "transactions": transactions,
+            "rewards": rewards,
+            "blockTime": null,
+            "blockHeight": 10,
+        }))
+        .unwrap()
+    }
 
+    fn reward(pubkey: &str, lamports: i64, reward_type: &str) -> serde_json::Value {
+        json!({ "pubkey": pubkey, "lamports": lamports, "postBalance": 1_000_000, "rewardType": reward_type })
+    }
+
+    #[test]
+    fn splits_fees_between_leader_and_burn() {
+        let block = block(&[5000, 7000], json!([reward("leader", 6000, "Fee")]));
+        assert_eq!(block.total_fees(), Some(Lamports(12_000)));
+        assert_eq!(block.leader_fees(), Some(Lamports(6000)));
+        assert_eq!(block.burned_fees(), Some(Lamports(6000)));
+    }
+
+    #[test]
+    fn fee_totals_need_transactions_and_rewards() {
+        let mut without_rewards = block(&[5000], json!(null));
+        assert_eq!(without_rewards.total_fees(), Some(Lamports(5000)));
+        assert_eq!(without_rewards.leader_fees(), None);
+        assert_eq!(without_rewards.burned_fees(), None);
+
+        without_rewards.transactions.as_mut().unwrap()[0].meta = None;
+        assert_eq!(without_rewards.total_fees(), None);
+
+        let mut without_transactions = block(&[], json!([reward("leader", 0, "Fee")]));
+        without_transactions.transactions = None;
+        assert_eq!(without_transactions.total_fees(), None);
+        assert_eq!(without_transactions.burned_fees(), None);
+    }
+
+    #[test]
+    fn groups_epoch_rewards() {
+        let block = block(
+            &[],
+            json!([
+                reward("stake-a", 100, "Staking"),
+                reward("stake-b", 50, "Staking"),
+                reward("stake-c", 25, "Staking"),
+                reward("vote", 10, "Voting"),
+            ]),
+        );
+
+        assert_eq!(block.staking_rewards().len(), 3);
+        assert_eq!(block.voting_rewards()["vote"], Lamports(10));
+
+        let delegations = HashMap::from([
+            ("stake-a".to_string(), "vote".to_string()),
+            ("stake-b".to_string(), "vote".to_string()),
+        ]);
+        let by_vote_account = block.staking_rewards_by_vote_account(&delegations);
+        assert_eq!(by_vote_account.len(), 1);
+        assert_eq!(by_vote_account["vote"], Lamports(150));
+    }
+}
@@ -1,11 +1,79 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// How settled the state a request reads must be. Ordered from least to
+/// most settled, so `Commitment::Processed < Commitment::Finalized`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Commitment {
-    Finalized,
+    Processed,
     Confirmed,
+    Finalized,
+}
+
+/// How settled a transaction is, as reported by `getSignatureStatuses`.
+/// Ordered like [`Commitment`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionConfirmationStatus {
     Processed,
+    Confirmed,
+    Finalized,
 }
 
+impl TransactionConfirmationStatus {
+    /// The commitment this status meets.
+    pub fn commitment(self) -> Commitment {
+        match self {
+            TransactionConfirmationStatus::Processed => Commitment::Processed,
+            TransactionConfirmationStatus::Confirmed => Commitment::Confirmed,
+            TransactionConfirmationStatus::Finalized => Commitment::Finalized,
+        }
+    }
 
+    pub fn satisfies(self, commitment: Commitment) -> bool {
+        self.commitment() >= commitment
+    }
+}
+
+impl From<TransactionConfirmationStatus> for Commitment {
+    fn from(status: TransactionConfirmationStatus) -> Self {
+        status.commitment()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commitments_are_ordered_by_how_settled_they_are() {
+        assert!(Commitment::Processed < Commitment::Confirmed);
+        assert!(Commitment::Confirmed < Commitment::Finalized);
+        assert_eq!(
+            [Commitment::Finalized, Commitment::Processed, Commitment::Confirmed].iter().max(),
+            Some(&Commitment::Finalized)
+        );
+    }
+
+    #[test]
+    fn statuses_satisfy_their_commitment_and_weaker_ones() {
+        use TransactionConfirmationStatus::*;
+
+        assert!(Processed.satisfies(Commitment::Processed));
+        assert!(!Processed.satisfies(Commitment::Confirmed));
+        assert!(Confirmed.satisfies(Commitment::Processed));
+        assert!(Confirmed.satisfies(Commitment::Confirmed));
+        assert!(!Confirmed.satisfies(Commitment::Finalized));
+        assert!(Finalized.satisfies(Commitment::Finalized));
+        assert_eq!(Commitment::from(Confirmed), Commitment::Confirmed);
+    }
+
+    #[test]
+    fn serializes_in_lowercase() {
+        assert_eq!(serde_json::to_value(Commitment::Confirmed).unwrap(), "confirmed");
+        assert_eq!(
+            serde_json::from_value::<TransactionConfirmationStatus>("finalized".into()).unwrap(),
+            TransactionConfirmationStatus::Finalized
+        );
+    }
+}
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 pub enum Encoding {
     Base58,
     Base64,
+    #[serde(rename = "base64+zstd")]
+    Base64Zstd,
     JsonParsed,
     #[serde(rename = "json")]
     Json,
@@ -0,0 +1,161 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseBytesError {
+    #[error("Invalid base58 string {0:?}")]
+    Base58(String),
+
+    #[error("Expected {expected} bytes, got {actual}")]
+    Length { expected: usize, actual: usize },
+}
+
+// Fixed-size values that travel as base58 strings. They are checked when
+// parsed, so a malformed one fails locally rather than at the node.
+macro_rules! base58_bytes {
+    ($(#[$meta:meta])* $name:ident, $len:expr) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name([u8; $len]);
+
+        impl $name {
+            pub const LEN: usize = $len;
+
+            pub const fn new(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+
+            pub const fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+
+            pub const fn to_bytes(self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl From<[u8; $len]> for $name {
+            fn from(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = ParseBytesError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                bytes.try_into().map(Self).map_err(|_| ParseBytesError::Length {
+                    expected: $len,
+                    actual: bytes.len(),
+                })
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseBytesError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let bytes = bs58::decode(s)
+                    .into_vec()
+                    .map_err(|_| ParseBytesError::Base58(s.to_string()))?;
+                Self::try_from(bytes.as_slice())
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&bs58::encode(&self.0).into_string())
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+base58_bytes!(
+    /// A 64-byte ed25519 transaction signature.
+    Signature,
+    64
+);
+
+base58_bytes!(
+    /// A 32-byte SHA-256 hash, such as a blockhash.
+    Hash,
+    32
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIGNATURE: &str =
+        "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+    const BLOCKHASH: &str = "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N";
+
+    #[test]
+    fn round_trips_through_base58() {
+        let signature: Signature = SIGNATURE.parse().unwrap();
+        assert_eq!(signature.to_string(), SIGNATURE);
+        assert_eq!(Signature::try_from(signature.as_ref()), Ok(signature));
+
+        let hash: Hash = BLOCKHASH.parse().unwrap();
+        assert_eq!(hash.to_string(), BLOCKHASH);
+        assert_eq!(format!("{:?}", hash), format!("Hash({})", BLOCKHASH));
+        assert_eq!(Hash::new([0; 32]).to_string(), "11111111111111111111111111111111");
+    }
+
+    #[test]
+    fn rejects_the_wrong_length() {
+        assert_eq!(
+            BLOCKHASH.parse::<Signature>(),
+            Err(ParseBytesError::Length { expected: 64, actual: 32 })
+        );
+        assert_eq!(
+            SIGNATURE.parse::<Hash>(),
+            Err(ParseBytesError::Length { expected: 32, actual: 64 })
+        );
+        assert_eq!("".parse::<Hash>(), Err(ParseBytesError::Length { expected: 32, actual: 0 }));
+    }
+
+    #[test]
+    fn rejects_invalid_base58() {
+        // 0, O, I and l are not in the base58 alphabet.
+        for input in ["0kSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N", "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKNlN"] {
+            assert_eq!(input.parse::<Hash>(), Err(ParseBytesError::Base58(input.to_string())));
+        }
+    }
+
+    #[test]
+    fn serializes_as_a_string() {
+        let hash: Hash = BLOCKHASH.parse().unwrap();
+        let json = serde_json::to_value(hash).unwrap();
+        assert_eq!(json, serde_json::json!(BLOCKHASH));
+        assert_eq!(serde_json::from_value::<Hash>(json).unwrap(), hash);
+        assert!(serde_json::from_value::<Signature>(serde_json::json!(BLOCKHASH)).is_err());
+    }
+}
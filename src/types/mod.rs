@@ -1,18 +1,30 @@
 pub mod account;
+pub mod amount;
 pub mod block;
 pub mod commitment;
 pub mod encoding;
+pub mod hash;
+pub mod parsed;
 pub mod pubkey;
+pub mod response;
+pub mod reward;
+pub mod slot;
 pub mod transaction;
 
-pub use account::{Account, AccountInfo};
+pub use account::{Account, AccountData, AccountDataError, AccountInfo};
+pub use amount::{AmountError, Lamports, TokenQuantity, LAMPORTS_PER_SOL};
 pub use block::{Block, BlockEncoding};
-pub use commitment::Commitment;
+pub use commitment::{Commitment, TransactionConfirmationStatus};
 pub use encoding::Encoding;
+pub use hash::{Hash, ParseBytesError, Signature};
+pub use parsed::ParsedAccount;
 pub use pubkey::Pubkey;
-pub use transaction::{Transaction, TransactionEncoding};
+pub use response::{Response, RpcResponse};
+pub use reward::{Reward, RewardType};
+pub use slot::{Epoch, Slot};
+pub use transaction::{BlockTransaction, Transaction, TransactionEncoding, TransactionMeta};
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct RpcContext {
     pub slot: u64,
 }
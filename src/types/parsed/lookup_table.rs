@@ -0,0 +1,23 @@
+use super::string_u64;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum LookupTableAccountType {
+    Uninitialized,
+    LookupTable(UiLookupTable),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiLookupTable {
+    /// `u64::MAX` while the table is active.
+    #[serde(with = "string_u64")]
+    pub deactivation_slot: u64,
+    #[serde(with = "string_u64")]
+    pub last_extended_slot: u64,
+    pub last_extended_slot_start_index: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authority: Option<String>,
+    pub addresses: Vec<String>,
+}
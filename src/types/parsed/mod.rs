@@ -0,0 +1,278 @@
+//! Typed models of `jsonParsed` account data.
+//!
+//! The node only parses accounts owned by the programs below; everything else
+//! comes back as binary data. Numbers that the node sends as strings to avoid
+//! precision loss in JavaScript are exposed as integers, except token amounts
+//! and supplies, which are kept as sent alongside their decimals. Read those
+//! exactly with [`TokenAmount::quantity`] and [`UiMint::supply_quantity`].
+
+pub mod lookup_table;
+pub mod nonce;
+pub mod stake;
+pub mod sysvar;
+pub mod token;
+pub mod upgradeable_loader;
+pub mod vote;
+
+pub use lookup_table::{LookupTableAccountType, UiLookupTable};
+pub use nonce::{NonceAccountType, UiFeeCalculator, UiNonceData};
+pub use stake::{StakeAccountType, UiStakeAccount};
+pub use sysvar::SysvarAccountType;
+pub use token::{TokenAccountState, TokenAccountType, TokenAmount, UiMint, UiMultisig, UiTokenAccount};
+pub use upgradeable_loader::UpgradeableLoaderAccountType;
+pub use vote::{UiVoteState, VoteAccountType};
+
+use serde::{Deserialize, Serialize};
+
+/// A `jsonParsed` account, keyed on the `program` that produced it and the
+/// `type` of account within that program.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "program", content = "parsed", rename_all = "kebab-case")]
+pub enum ParsedAccount {
+    SplToken(TokenAccountType),
+    #[serde(rename = "spl-token-2022")]
+    SplToken2022(TokenAccountType),
+    Stake(StakeAccountType),
+    Vote(VoteAccountType),
+    Nonce(NonceAccountType),
+    AddressLookupTable(LookupTableAccountType),
+    BpfUpgradeableLoader(UpgradeableLoaderAccountType),
+    Sysvar(SysvarAccountType),
+}
+
+impl ParsedAccount {
+    /// The token account, for `spl-token` and `spl-token-2022` accounts.
+    pub fn token_account(&self) -> Option<&UiTokenAccount> {
+        match self {
+            ParsedAccount::SplToken(TokenAccountType::Account(account))
+            | ParsedAccount::SplToken2022(TokenAccountType::Account(account)) => Some(account),
+            _ => None,
+        }
+    }
+
+    /// The mint, for `spl-token` and `spl-token-2022` accounts.
+    pub fn mint(&self) -> Option<&UiMint> {
+        match self {
+            ParsedAccount::SplToken(TokenAccountType::Mint(mint))
+            | ParsedAccount::SplToken2022(TokenAccountType::Mint(mint)) => Some(mint),
+            _ => None,
+        }
+    }
+}
+
+// (De)serializes a `u64` sent as a decimal string. Plain numbers are accepted
+// too.
+pub(crate) mod string_u64 {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &u64, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StringOrNumber {
+            String(String),
+            Number(u64),
+        }
+
+        match StringOrNumber::deserialize(deserializer)? {
+            StringOrNumber::String(value) => value.parse().map_err(de::Error::custom),
+            StringOrNumber::Number(value) => Ok(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PUBKEY: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    const HASH: &str = "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N";
+
+    fn parse(program: &str, kind: &str, info: serde_json::Value) -> ParsedAccount {
+        serde_json::from_value(json!({
+            "program": program,
+            "parsed": { "type": kind, "info": info },
+            "space": 165,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn parses_token_accounts_of_both_programs() {
+        let info = json!({
+            "isNative": false,
+            "mint": PUBKEY,
+            "owner": PUBKEY,
+            "state": "frozen",
+            "tokenAmount": { "amount": "1500000", "decimals": 6, "uiAmount": 1.5, "uiAmountString": "1.5" },
+        });
+
+        for program in ["spl-token", "spl-token-2022"] {
+            let parsed = parse(program, "account", info.clone());
+            let account = parsed.token_account().unwrap();
+            assert_eq!(account.state, TokenAccountState::Frozen);
+            assert_eq!(account.token_amount.quantity().unwrap().to_string(), "1.5");
+            assert!(account.delegate.is_none());
+            assert!(parsed.mint().is_none());
+        }
+    }
+
+    #[test]
+    fn parses_mints_with_extensions() {
+        let parsed = parse(
+            "spl-token-2022",
+            "mint",
+            json!({
+                "mintAuthority": PUBKEY,
+                "supply": "18446744073709551615",
+                "decimals": 9,
+                "isInitialized": true,
+                "freezeAuthority": null,
+                "extensions": [{ "extension": "mintCloseAuthority", "state": { "closeAuthority": PUBKEY } }],
+            }),
+        );
+
+        let mint = parsed.mint().unwrap();
+        assert_eq!(mint.supply_quantity().unwrap().to_u64(), Some(u64::MAX));
+        assert_eq!(mint.extensions.len(), 1);
+        assert!(mint.freeze_authority.is_none());
+    }
+
+    #[test]
+    fn parses_delegated_stake() {
+        let parsed = parse(
+            "stake",
+            "delegated",
+            json!({
+                "meta": {
+                    "rentExemptReserve": "2282880",
+                    "authorized": { "staker": PUBKEY, "withdrawer": PUBKEY },
+                    "lockup": { "unixTimestamp": 0, "epoch": 0, "custodian": "11111111111111111111111111111111" },
+                },
+                "stake": {
+                    "delegation": {
+                        "voter": PUBKEY,
+                        "stake": "1000000000",
+                        "activationEpoch": "500",
+                        "deactivationEpoch": "18446744073709551615",
+                        "warmupCooldownRate": 0.25,
+                    },
+                    "creditsObserved": 12345,
+                },
+            }),
+        );
+
+        let ParsedAccount::Stake(StakeAccountType::Delegated(stake)) = parsed else {
+            panic!("expected a delegated stake account, got {:?}", parsed);
+        };
+        assert_eq!(stake.meta.rent_exempt_reserve, 2_282_880);
+        let delegation = stake.stake.unwrap().delegation;
+        assert_eq!(delegation.stake, 1_000_000_000);
+        assert_eq!(delegation.deactivation_epoch, u64::MAX);
+    }
+
+    #[test]
+    fn parses_vote_nonce_and_lookup_table_accounts() {
+        let vote = parse(
+            "vote",
+            "vote",
+            json!({
+                "nodePubkey": PUBKEY,
+                "authorizedWithdrawer": PUBKEY,
+                "commission": 10,
+                "votes": [{ "slot": 100, "confirmationCount": 31 }],
+                "rootSlot": 69,
+                "authorizedVoters": [{ "epoch": 5, "authorizedVoter": PUBKEY }],
+                "priorVoters": [],
+                "epochCredits": [{ "epoch": 5, "credits": "1200", "previousCredits": "800" }],
+                "lastTimestamp": { "slot": 100, "timestamp": 1700000000 },
+            }),
+        );
+        let ParsedAccount::Vote(VoteAccountType::Vote(vote)) = vote else {
+            panic!("expected a vote account");
+        };
+        assert_eq!(vote.epoch_credits[0].credits, 1200);
+
+        let nonce = parse(
+            "nonce",
+            "initialized",
+            json!({
+                "authority": PUBKEY,
+                "blockhash": HASH,
+                "feeCalculator": { "lamportsPerSignature": "5000" },
+            }),
+        );
+        let ParsedAccount::Nonce(NonceAccountType::Initialized(nonce)) = nonce else {
+            panic!("expected an initialized nonce account");
+        };
+        assert_eq!(nonce.blockhash.to_string(), HASH);
+        assert_eq!(nonce.fee_calculator.lamports_per_signature, 5000);
+
+        let table = parse(
+            "address-lookup-table",
+            "lookupTable",
+            json!({
+                "deactivationSlot": "18446744073709551615",
+                "lastExtendedSlot": "250",
+                "lastExtendedSlotStartIndex": 0,
+                "addresses": [PUBKEY],
+            }),
+        );
+        let ParsedAccount::AddressLookupTable(LookupTableAccountType::LookupTable(table)) = table else {
+            panic!("expected a lookup table");
+        };
+        assert_eq!(table.last_extended_slot, 250);
+        assert!(table.authority.is_none());
+    }
+
+    #[test]
+    fn parses_sysvars_and_programs() {
+        let clock = parse(
+            "sysvar",
+            "clock",
+            json!({
+                "slot": 1000,
+                "epoch": 2,
+                "epochStartTimestamp": 1699990000,
+                "leaderScheduleEpoch": 3,
+                "unixTimestamp": 1700000000,
+            }),
+        );
+        assert!(matches!(
+            clock,
+            ParsedAccount::Sysvar(SysvarAccountType::Clock(ref clock)) if clock.slot == 1000
+        ));
+
+        let program = parse("bpf-upgradeable-loader", "program", json!({ "programData": PUBKEY }));
+        assert!(matches!(
+            program,
+            ParsedAccount::BpfUpgradeableLoader(UpgradeableLoaderAccountType::Program(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_programs() {
+        let data = json!({ "program": "spl-memo", "parsed": {}, "space": 0 });
+        assert!(serde_json::from_value::<ParsedAccount>(data).is_err());
+    }
+
+    #[test]
+    fn reads_u64_strings_and_numbers() {
+        #[derive(Deserialize)]
+        struct Value(#[serde(with = "string_u64")] u64);
+
+        assert_eq!(serde_json::from_value::<Value>(json!("42")).unwrap().0, 42);
+        assert_eq!(serde_json::from_value::<Value>(json!(42)).unwrap().0, 42);
+        assert!(serde_json::from_value::<Value>(json!("-1")).is_err());
+    }
+}
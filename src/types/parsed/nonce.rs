@@ -0,0 +1,26 @@
+use super::string_u64;
+use crate::types::Hash;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum NonceAccountType {
+    Uninitialized,
+    Initialized(UiNonceData),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiNonceData {
+    pub authority: String,
+    /// The durable nonce, used in place of a recent blockhash.
+    pub blockhash: Hash,
+    pub fee_calculator: UiFeeCalculator,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiFeeCalculator {
+    #[serde(with = "string_u64")]
+    pub lamports_per_signature: u64,
+}
@@ -0,0 +1,87 @@
+use crate::types::{AmountError, TokenQuantity};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+#[allow(clippy::large_enum_variant)]
+pub enum TokenAccountType {
+    Account(UiTokenAccount),
+    Mint(UiMint),
+    Multisig(UiMultisig),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAmount {
+    /// The amount as a float, which can round; see
+    /// [`TokenQuantity::to_f64_lossy`].
+    pub ui_amount: Option<f64>,
+    pub decimals: u8,
+    pub amount: String,
+    pub ui_amount_string: String,
+}
+
+impl TokenAmount {
+    /// The exact amount.
+    pub fn quantity(&self) -> Result<TokenQuantity, AmountError> {
+        TokenQuantity::from_raw(&self.amount, self.decimals)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TokenAccountState {
+    Uninitialized,
+    Initialized,
+    Frozen,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiTokenAccount {
+    pub mint: String,
+    pub owner: String,
+    pub token_amount: TokenAmount,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegate: Option<String>,
+    pub state: TokenAccountState,
+    pub is_native: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rent_exempt_reserve: Option<TokenAmount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegated_amount: Option<TokenAmount>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub close_authority: Option<String>,
+    /// Token-2022 extensions, as parsed by the node.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiMint {
+    pub mint_authority: Option<String>,
+    pub supply: String,
+    pub decimals: u8,
+    pub is_initialized: bool,
+    pub freeze_authority: Option<String>,
+    /// Token-2022 extensions, as parsed by the node.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<serde_json::Value>,
+}
+
+impl UiMint {
+    /// The exact supply.
+    pub fn supply_quantity(&self) -> Result<TokenQuantity, AmountError> {
+        TokenQuantity::from_raw(&self.supply, self.decimals)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiMultisig {
+    pub num_required_signers: u8,
+    pub num_valid_signers: u8,
+    pub is_initialized: bool,
+    pub signers: Vec<String>,
+}
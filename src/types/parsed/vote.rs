@@ -0,0 +1,61 @@
+use super::string_u64;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "info", rename_all = "camelCase")]
+pub enum VoteAccountType {
+    Vote(UiVoteState),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiVoteState {
+    pub node_pubkey: String,
+    pub authorized_withdrawer: String,
+    pub commission: u8,
+    pub votes: Vec<UiLockout>,
+    pub root_slot: Option<u64>,
+    pub authorized_voters: Vec<UiAuthorizedVoter>,
+    pub prior_voters: Vec<UiPriorVoter>,
+    pub epoch_credits: Vec<UiEpochCredits>,
+    pub last_timestamp: UiBlockTimestamp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiLockout {
+    pub slot: u64,
+    pub confirmation_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiAuthorizedVoter {
+    pub epoch: u64,
+    pub authorized_voter: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiPriorVoter {
+    pub authorized_pubkey: String,
+    pub epoch_of_last_authorized_switch: u64,
+    pub target_epoch: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiEpochCredits {
+    pub epoch: u64,
+    #[serde(with = "string_u64")]
+    pub credits: u64,
+    #[serde(with = "string_u64")]
+    pub previous_credits: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiBlockTimestamp {
+    pub slot: u64,
+    pub timestamp: i64,
+}
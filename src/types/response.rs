@@ -0,0 +1,151 @@
+use super::RpcContext;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// The result of any RPC method.
+///
+/// Methods that report the slot they were evaluated at wrap their result in
+/// `{"context": ..., "value": ...}`; others return the value directly, in
+/// which case `context` is `None`. Which shape a method returns is known from
+/// its request, see [`RpcRequest::has_context`](crate::client::RpcRequest::has_context).
+///
+/// Serializes as `{"context": ..., "value": ...}`, leaving out `context`
+/// when it is `None`, and deserializes from that same form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response<T> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<RpcContext>,
+    pub value: T,
+}
+
+impl<T> Response<T> {
+    pub fn new(value: T) -> Self {
+        Self { context: None, value }
+    }
+
+    pub fn with_context(context: RpcContext, value: T) -> Self {
+        Self {
+            context: Some(context),
+            value,
+        }
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn context(&self) -> Option<&RpcContext> {
+        self.context.as_ref()
+    }
+
+    /// The slot the response was evaluated at, when the node reported one.
+    pub fn slot(&self) -> Option<u64> {
+        self.context.as_ref().map(|context| context.slot)
+    }
+
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Response<U> {
+        Response {
+            context: self.context,
+            value: f(self.value),
+        }
+    }
+}
+
+/// Access to the value and context of a response, for code that is generic
+/// over [`RpcRequest`](crate::client::RpcRequest)s.
+pub trait RpcResponse: Sized {
+    type Value;
+
+    /// Reads a method's `result`, which is wrapped in
+    /// `{"context": ..., "value": ...}` when `with_context` is set.
+    fn from_result<'de, D>(result: D, with_context: bool) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>;
+
+    fn value(&self) -> &Self::Value;
+
+    fn context(&self) -> Option<&RpcContext>;
+
+    fn into_value(self) -> Self::Value;
+
+    fn slot(&self) -> Option<u64> {
+        self.context().map(|context| context.slot)
+    }
+}
+
+impl<T: DeserializeOwned> RpcResponse for Response<T> {
+    type Value = T;
+
+    fn from_result<'de, D>(result: D, with_context: bool) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Wrapped<T> {
+            context: RpcContext,
+            value: T,
+        }
+
+        if with_context {
+            let Wrapped { context, value } = Wrapped::deserialize(result)?;
+            Ok(Self::with_context(context, value))
+        } else {
+            T::deserialize(result).map(Self::new)
+        }
+    }
+
+    fn value(&self) -> &T {
+        &self.value
+    }
+
+    fn context(&self) -> Option<&RpcContext> {
+        self.context.as_ref()
+    }
+
+    fn into_value(self) -> T {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn reads_a_bare_result() {
+        let response = Response::<u64>::from_result(json!(42), false).unwrap();
+        assert_eq!(response, Response::new(42));
+    }
+
+    #[test]
+    fn reads_a_result_with_context() {
+        let result = json!({ "context": { "slot": 7, "apiVersion": "2.0.0" }, "value": 42 });
+        let response = Response::<u64>::from_result(result, true).unwrap();
+        assert_eq!(response, Response::with_context(RpcContext { slot: 7 }, 42));
+    }
+
+    // A bare value shaped like a context wrapper stays a value.
+    #[test]
+    fn does_not_guess_the_shape() {
+        let result = json!({ "context": { "slot": 7 }, "value": 42 });
+        let response = Response::<Value>::from_result(result.clone(), false).unwrap();
+        assert_eq!(response.context, None);
+        assert_eq!(response.value, result);
+
+        assert!(Response::<u64>::from_result(json!(42), true).is_err());
+    }
+
+    #[test]
+    fn serialization_round_trips() {
+        for response in [Response::new(42), Response::with_context(RpcContext { slot: 7 }, 42)] {
+            let json = serde_json::to_value(&response).unwrap();
+            assert_eq!(serde_json::from_value::<Response<u64>>(json).unwrap(), response);
+        }
+        assert_eq!(serde_json::to_value(Response::new(42)).unwrap(), json!({ "value": 42 }));
+    }
+}
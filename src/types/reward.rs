@@ -0,0 +1,121 @@
+use super::Lamports;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RewardType {
+    /// Transaction fees paid to the slot leader.
+    Fee,
+    /// Rent collected by the slot leader, or debited from an account.
+    Rent,
+    /// Inflation rewards paid to a stake account.
+    Staking,
+    /// Commission on inflation rewards paid to a vote account.
+    Voting,
+}
+
+/// A balance change credited or debited by the runtime rather than by a
+/// transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reward {
+    pub pubkey: String,
+    /// Negative for debits, such as rent collected from an account.
+    pub lamports: i64,
+    pub post_balance: Lamports,
+    pub reward_type: Option<RewardType>,
+    /// The vote account's commission, for staking and voting rewards.
+    #[serde(default)]
+    pub commission: Option<u8>,
+}
+
+impl Reward {
+    /// The amount credited, or `None` for a debit.
+    pub fn credit(&self) -> Option<Lamports> {
+        u64::try_from(self.lamports).ok().map(Lamports)
+    }
+}
+
+/// Sums the credits of the rewards of type `reward_type`.
+pub fn total_rewards<'a>(rewards: impl IntoIterator<Item = &'a Reward>, reward_type: RewardType) -> Lamports {
+    rewards
+        .into_iter()
+        .filter(|reward| reward.reward_type == Some(reward_type))
+        .filter_map(Reward::credit)
+        .fold(Lamports::ZERO, Lamports::saturating_add)
+}
+
+/// Sums the credits of the rewards of type `reward_type` per account.
+pub fn rewards_by_account<'a>(
+    rewards: impl IntoIterator<Item = &'a Reward>,
+    reward_type: RewardType,
+) -> HashMap<String, Lamports> {
+    let mut totals: HashMap<String, Lamports> = HashMap::new();
+    for reward in rewards {
+        if reward.reward_type != Some(reward_type) {
+            continue;
+        }
+        if let Some(credit) = reward.credit() {
+            let total = totals.entry(reward.pubkey.clone()).or_default();
+            *total = total.saturating_add(credit);
+        }
+    }
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn reward(pubkey: &str, lamports: i64, reward_type: RewardType) -> Reward {
+        Reward {
+            pubkey: pubkey.to_string(),
+            lamports,
+            post_balance: Lamports(1_000_000),
+            reward_type: Some(reward_type),
+            commission: None,
+        }
+    }
+
+    #[test]
+    fn deserializes_rpc_rewards() {
+        let reward: Reward = serde_json::from_value(json!({
+            "pubkey": "vote",
+            "lamports": -2000,
+            "postBalance": 998000,
+            "rewardType": "Rent",
+        }))
+        .unwrap();
+        assert_eq!(reward.reward_type, Some(RewardType::Rent));
+        assert_eq!(reward.commission, None);
+        assert_eq!(reward.credit(), None);
+
+        let reward: Reward = serde_json::from_value(json!({
+            "pubkey": "stake",
+            "lamports": 2000,
+            "postBalance": 1002000,
+            "rewardType": null,
+            "commission": 7,
+        }))
+        .unwrap();
+        assert_eq!(reward.reward_type, None);
+        assert_eq!(reward.credit(), Some(Lamports(2000)));
+    }
+
+    #[test]
+    fn totals_credits_of_one_type() {
+        let rewards = [
+            reward("leader", 5000, RewardType::Fee),
+            reward("leader", 3000, RewardType::Fee),
+            reward("account", -100, RewardType::Fee),
+            reward("stake", 9000, RewardType::Staking),
+        ];
+        assert_eq!(total_rewards(&rewards, RewardType::Fee), Lamports(8000));
+        assert_eq!(total_rewards(&rewards, RewardType::Voting), Lamports::ZERO);
+
+        let by_account = rewards_by_account(&rewards, RewardType::Fee);
+        assert_eq!(by_account.len(), 1);
+        assert_eq!(by_account["leader"], Lamports(8000));
+    }
+}
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A slot number.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Slot(pub u64);
+
+/// An epoch number.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Epoch(pub u64);
+
+impl Slot {
+    pub const fn new(slot: u64) -> Self {
+        Self(slot)
+    }
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    pub fn checked_add(self, slots: u64) -> Option<Slot> {
+        self.0.checked_add(slots).map(Slot)
+    }
+
+    pub fn checked_sub(self, slots: u64) -> Option<Slot> {
+        self.0.checked_sub(slots).map(Slot)
+    }
+
+    /// The number of slots from `earlier` to this slot, or `None` if
+    /// `earlier` is later.
+    pub fn slots_since(self, earlier: Slot) -> Option<u64> {
+        self.0.checked_sub(earlier.0)
+    }
+}
+
+impl Epoch {
+    pub const fn new(epoch: u64) -> Self {
+        Self(epoch)
+    }
+
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    pub fn next(self) -> Epoch {
+        Epoch(self.0.saturating_add(1))
+    }
+
+    pub fn previous(self) -> Option<Epoch> {
+        self.0.checked_sub(1).map(Epoch)
+    }
+}
+
+macro_rules! u64_conversions {
+    ($name:ident) => {
+        impl From<u64> for $name {
+            fn from(value: u64) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+    };
+}
+
+u64_conversions!(Slot);
+u64_conversions!(Epoch);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slot_arithmetic_is_checked() {
+        assert_eq!(Slot(10).slots_since(Slot(4)), Some(6));
+        assert_eq!(Slot(4).slots_since(Slot(10)), None);
+        assert_eq!(Slot(u64::MAX).checked_add(1), None);
+        assert_eq!(Slot(0).checked_sub(1), None);
+    }
+
+    #[test]
+    fn epochs_step_without_overflowing() {
+        assert_eq!(Epoch(5).next(), Epoch(6));
+        assert_eq!(Epoch(u64::MAX).next(), Epoch(u64::MAX));
+        assert_eq!(Epoch(0).previous(), None);
+    }
+
+    #[test]
+    fn serializes_as_a_number() {
+        assert_eq!(serde_json::to_value(Slot(42)).unwrap(), 42);
+        assert_eq!(serde_json::from_value::<Epoch>(7.into()).unwrap(), Epoch(7));
+        assert_eq!(Slot(42).to_string(), "42");
+    }
+}
@@ -1,3 +1,4 @@
+use super::{Lamports, Reward};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -11,11 +12,37 @@ pub enum TransactionEncoding {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Transaction {
     pub slot: Option<u64>,
     pub transaction: Option<serde_json::Value>,
-    pub meta: Option<serde_json::Value>,
+    pub meta: Option<TransactionMeta>,
     pub block_time: Option<i64>,
 }
 
+/// Status metadata of a processed transaction. Fields without a typed
+/// counterpart are kept in `other` as the node returned them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionMeta {
+    pub err: Option<serde_json::Value>,
+    pub fee: Lamports,
+    #[serde(default)]
+    pub pre_balances: Vec<Lamports>,
+    #[serde(default)]
+    pub post_balances: Vec<Lamports>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rewards: Option<Vec<Reward>>,
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
 
+/// A transaction within a block, as returned by `getBlock`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockTransaction {
+    pub transaction: serde_json::Value,
+    pub meta: Option<TransactionMeta>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<serde_json::Value>,
+}
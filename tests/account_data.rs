@@ -0,0 +1,58 @@
+use solana_jsonrpc_client::methods::account::{AccountInfoConfig, DataSliceConfig, RpcGetAccountInfoRequest};
+use solana_jsonrpc_client::test_utils::{MockAccount, MockServer};
+use solana_jsonrpc_client::types::{Account, AccountDataError, AccountInfo, Encoding};
+
+const OWNER: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+async fn account_info(server: &MockServer, encoding: Encoding, data_slice: Option<DataSliceConfig>) -> AccountInfo {
+    server
+        .client()
+        .call(RpcGetAccountInfoRequest {
+            pubkey: "account".to_string(),
+            config: Some(AccountInfoConfig {
+                encoding: Some(encoding),
+                commitment: None,
+                data_slice,
+                min_context_slot: None,
+            }),
+        })
+        .await
+        .unwrap()
+        .into_value()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn decodes_every_binary_encoding() {
+    let server = MockServer::start().await;
+    let data: Vec<u8> = (0..100).collect();
+    server
+        .ledger()
+        .set_account("account", MockAccount::system(1).with_data(OWNER, data.clone()));
+
+    for encoding in [Encoding::Base58, Encoding::Base64, Encoding::Base64Zstd] {
+        let info = account_info(&server, encoding, None).await;
+        assert_eq!(info.data_bytes().unwrap(), data);
+
+        let account = Account::try_from(info).unwrap();
+        assert_eq!(account.data, data);
+        assert_eq!(account.owner, OWNER);
+    }
+}
+
+#[tokio::test]
+async fn sliced_data_is_reported_as_truncated() {
+    let server = MockServer::start().await;
+    server
+        .ledger()
+        .set_account("account", MockAccount::system(1).with_data(OWNER, vec![7; 64]));
+
+    let info = account_info(&server, Encoding::Base64, Some(DataSliceConfig { offset: 8, length: 16 })).await;
+
+    assert!(matches!(
+        info.data_bytes(),
+        Err(AccountDataError::Truncated { actual: 16, space: 64 })
+    ));
+    assert_eq!(info.data_encoded.decode().unwrap(), vec![7; 16]);
+    assert!(Account::try_from(info).is_err());
+}
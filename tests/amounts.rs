@@ -0,0 +1,55 @@
+use solana_jsonrpc_client::methods::account::RpcGetBalanceRequest;
+use solana_jsonrpc_client::methods::token::RpcGetTokenSupplyRequest;
+use solana_jsonrpc_client::test_utils::{MockAccount, MockServer};
+use solana_jsonrpc_client::types::{Lamports, TokenQuantity};
+
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+fn mint(supply: u64, decimals: u8) -> MockAccount {
+    let mut data = vec![0; 82];
+    data[36..44].copy_from_slice(&supply.to_le_bytes());
+    data[44] = decimals;
+    data[45] = 1;
+    MockAccount::system(1_461_600).with_data(TOKEN_PROGRAM, data)
+}
+
+#[tokio::test]
+async fn balances_are_lamports() {
+    let server = MockServer::start().await;
+    server.ledger().set_balance("wallet", 1_500_000_000);
+
+    let balance = server
+        .client()
+        .call(RpcGetBalanceRequest {
+            pubkey: "wallet".to_string(),
+            config: None,
+        })
+        .await
+        .unwrap()
+        .into_value();
+
+    assert_eq!(balance, Lamports(1_500_000_000));
+    assert_eq!(balance.to_string(), "1.5 SOL");
+}
+
+#[tokio::test]
+async fn token_supply_is_exact_beyond_f64_precision() {
+    let server = MockServer::start().await;
+    server.ledger().set_account("mint", mint(u64::MAX, 6));
+
+    let supply = server
+        .client()
+        .call(RpcGetTokenSupplyRequest {
+            mint: "mint".to_string(),
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap()
+        .into_value();
+
+    let quantity = supply.quantity().unwrap();
+    assert_eq!(quantity, TokenQuantity::new(u64::MAX, 6));
+    assert_eq!(quantity.to_string(), supply.ui_amount_string);
+    assert_eq!(quantity.to_string(), "18446744073709.551615");
+}
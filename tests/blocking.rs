@@ -0,0 +1,44 @@
+#![cfg(feature = "blocking")]
+
+use solana_jsonrpc_client::blocking::JsonRpcClient;
+use solana_jsonrpc_client::methods::account::RpcGetBalanceRequest;
+use solana_jsonrpc_client::test_utils::MockServer;
+use solana_jsonrpc_client::{CallOptions, JsonRpcError};
+use std::time::Duration;
+
+const PUBKEY: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+// The mock needs a runtime of its own, which must outlive the server.
+fn start_server() -> (tokio::runtime::Runtime, MockServer) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(MockServer::start());
+    (runtime, server)
+}
+
+fn request() -> RpcGetBalanceRequest {
+    RpcGetBalanceRequest {
+        pubkey: PUBKEY.to_string(),
+        config: None,
+    }
+}
+
+#[test]
+fn calls_without_a_runtime() {
+    let (_runtime, server) = start_server();
+    server.ledger().set_balance(PUBKEY, 5);
+
+    let client = JsonRpcClient::connect(server.url());
+    assert_eq!(client.call(request()).unwrap().value.get(), 5);
+    assert_eq!(client.clone().call(request()).unwrap().value.get(), 5);
+}
+
+#[test]
+fn applies_call_options() {
+    let (_runtime, server) = start_server();
+    server.set_latency(Duration::from_millis(500));
+
+    let client = JsonRpcClient::from_async(server.client());
+    let options = CallOptions::new().with_timeout(Duration::from_millis(20));
+    let err = client.call_with_options(request(), options).unwrap_err();
+    assert!(matches!(err, JsonRpcError::Timeout { .. }));
+}
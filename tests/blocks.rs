@@ -0,0 +1,41 @@
+use solana_jsonrpc_client::methods::block::{BlockConfig, RpcGetBlockRequest, TransactionDetails};
+use solana_jsonrpc_client::test_utils::MockServer;
+use solana_jsonrpc_client::types::Lamports;
+
+#[tokio::test]
+async fn block_fees_add_up() {
+    let server = MockServer::start().await;
+    let slot = {
+        let mut ledger = server.ledger();
+        ledger.land_transaction("first");
+        ledger.land_transaction("second");
+        let slot = ledger.slot;
+        ledger.advance_slots(1);
+        slot
+    };
+
+    let block = server
+        .client()
+        .call(RpcGetBlockRequest {
+            slot,
+            config: Some(BlockConfig {
+                encoding: None,
+                transaction_details: Some(TransactionDetails::Full),
+                rewards: Some(true),
+                commitment: None,
+                max_supported_transaction_version: Some(0),
+            }),
+        })
+        .await
+        .unwrap()
+        .into_value()
+        .unwrap();
+
+    let total = block.total_fees().unwrap();
+    assert_eq!(total, Lamports(10_000));
+    assert_eq!(
+        block.leader_fees().unwrap().checked_add(block.burned_fees().unwrap()),
+        Some(total)
+    );
+    assert_eq!(block.leader_fees(), Some(Lamports(5000)));
+}
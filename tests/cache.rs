@@ -0,0 +1,72 @@
+use solana_jsonrpc_client::methods::account::RpcGetBalanceRequest;
+use solana_jsonrpc_client::methods::chain::RpcGetGenesisHashRequest;
+use solana_jsonrpc_client::test_utils::MockServer;
+use solana_jsonrpc_client::{CachePolicy, ResponseCache};
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+fn cache() -> ResponseCache {
+    ResponseCache::lru(NonZeroUsize::new(16).unwrap())
+}
+
+#[tokio::test]
+async fn serves_permanent_results_from_cache() {
+    let server = MockServer::start().await;
+    let client = server.client().with_cache(cache());
+
+    let first = client.call(RpcGetGenesisHashRequest).await.unwrap();
+    let second = client.call(RpcGetGenesisHashRequest).await.unwrap();
+
+    assert_eq!(first.value, second.value);
+    assert_eq!(server.call_count("getGenesisHash"), 1);
+}
+
+#[tokio::test]
+async fn does_not_cache_methods_without_a_policy() {
+    let server = MockServer::start().await;
+    let client = server.client().with_cache(cache());
+    let pubkey = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    server.ledger().set_balance(pubkey, 1);
+    let request = || RpcGetBalanceRequest {
+        pubkey: pubkey.to_string(),
+        config: None,
+    };
+    client.call(request()).await.unwrap();
+    server.ledger().set_balance(pubkey, 2);
+    let balance = client.call(request()).await.unwrap();
+
+    assert_eq!(balance.value.get(), 2);
+    assert_eq!(server.call_count("getBalance"), 2);
+}
+
+#[tokio::test]
+async fn sessions_share_entries_until_they_need_a_newer_slot() {
+    let server = MockServer::start().await;
+    let cache = cache().with_policy("getBalance", CachePolicy::Ttl(Duration::from_secs(60)));
+    let client = server.client().with_cache(cache);
+    let session = client.session();
+    let pubkey = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+    let request = || RpcGetBalanceRequest {
+        pubkey: pubkey.to_string(),
+        config: None,
+    };
+
+    server.ledger().advance_slots(3);
+    client.call(request()).await.unwrap();
+    session.observe(3);
+    let cached = session.call(request()).await.unwrap();
+    assert_eq!(cached.slot(), Some(3));
+    assert_eq!(server.call_count("getBalance"), 1);
+
+    server.ledger().advance_slots(2);
+    session.observe(5);
+    let fresh = session.call(request()).await.unwrap();
+    assert_eq!(fresh.slot(), Some(5));
+    assert_eq!(server.call_count("getBalance"), 2);
+
+    // The newer result replaced the entry plain requests use too.
+    let plain = client.call(request()).await.unwrap();
+    assert_eq!(plain.slot(), Some(5));
+    assert_eq!(server.call_count("getBalance"), 2);
+}
@@ -0,0 +1,86 @@
+use solana_jsonrpc_client::methods::account::RpcGetBalanceRequest;
+use solana_jsonrpc_client::methods::chain::RpcGetGenesisHashRequest;
+use solana_jsonrpc_client::test_utils::{Fault, MockServer};
+use solana_jsonrpc_client::{Cassette, JsonRpcClient, JsonRpcError};
+use std::path::PathBuf;
+
+const PUBKEY: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("solana-jsonrpc-client-{}-{}.jsonl", std::process::id(), name))
+}
+
+fn balance_request() -> RpcGetBalanceRequest {
+    RpcGetBalanceRequest {
+        pubkey: PUBKEY.to_string(),
+        config: None,
+    }
+}
+
+// Nothing listens on this address, so replayed calls can only be answered
+// by the cassette.
+fn offline_client(path: &PathBuf) -> JsonRpcClient {
+    JsonRpcClient::connect("http://127.0.0.1:9").with_cassette(Cassette::replay(path).unwrap())
+}
+
+#[tokio::test]
+async fn replays_recorded_responses_in_order() {
+    let path = cassette_path("order");
+    let server = MockServer::start().await;
+    let client = server.client().with_cassette(Cassette::record(&path).unwrap());
+
+    server.ledger().set_balance(PUBKEY, 1);
+    client.call(balance_request()).await.unwrap();
+    server.ledger().set_balance(PUBKEY, 2);
+    client.call(balance_request()).await.unwrap();
+    let genesis_hash = client.call(RpcGetGenesisHashRequest).await.unwrap().value;
+    drop(server);
+
+    let client = offline_client(&path);
+    assert_eq!(client.call(balance_request()).await.unwrap().value.get(), 1);
+    assert_eq!(client.call(balance_request()).await.unwrap().value.get(), 2);
+    assert_eq!(client.call(balance_request()).await.unwrap().value.get(), 2);
+    assert_eq!(client.call(RpcGetGenesisHashRequest).await.unwrap().value, genesis_hash);
+
+    let err = client.call(RpcGetBalanceRequest {
+        pubkey: "unrecorded".to_string(),
+        config: None,
+    });
+    assert!(matches!(err.await, Err(JsonRpcError::Cassette(_))));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn replays_http_and_rpc_errors() {
+    let path = cassette_path("errors");
+    let server = MockServer::start().await;
+    let client = server.client().with_cassette(Cassette::record(&path).unwrap());
+
+    server.inject_fault("getBalance", Fault::Http(429));
+    server.inject_fault("getGenesisHash", Fault::rpc(-32005, "Node is behind"));
+    let http_error = client.call(balance_request()).await.unwrap_err().to_string();
+    assert!(http_error.contains("429"), "{}", http_error);
+    assert!(client.call(RpcGetGenesisHashRequest).await.is_err());
+    drop(server);
+
+    let client = offline_client(&path);
+    let replayed = client.call(balance_request()).await.unwrap_err();
+    assert_eq!(replayed.to_string(), http_error);
+    let replayed = client.call(RpcGetGenesisHashRequest).await.unwrap_err();
+    assert!(matches!(replayed, JsonRpcError::Rpc { code: -32005, .. }));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[tokio::test]
+async fn recording_respects_the_max_response_size() {
+    let path = cassette_path("size");
+    let server = MockServer::start().await;
+    let client = server
+        .client()
+        .with_max_response_size(16)
+        .with_cassette(Cassette::record(&path).unwrap());
+
+    let err = client.call(RpcGetGenesisHashRequest).await.unwrap_err();
+    assert!(matches!(err, JsonRpcError::ResponseTooLarge { limit: 16 }));
+    std::fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,60 @@
+use serde_json::json;
+use solana_jsonrpc_client::methods::account::RpcGetBalanceRequest;
+use solana_jsonrpc_client::test_utils::{Fault, MockServer};
+use solana_jsonrpc_client::JsonRpcError;
+use std::time::Duration;
+
+const PUBKEY: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+fn request() -> RpcGetBalanceRequest {
+    RpcGetBalanceRequest {
+        pubkey: PUBKEY.to_string(),
+        config: None,
+    }
+}
+
+async fn slow_server() -> MockServer {
+    let server = MockServer::start().await;
+    server.ledger().set_balance(PUBKEY, 42);
+    server.set_method_latency("getBalance", Duration::from_millis(100));
+    server
+}
+
+#[tokio::test]
+async fn identical_calls_share_one_request() {
+    let server = slow_server().await;
+    let client = server.client();
+
+    let (first, second) = tokio::join!(client.call_coalesced(request()), client.call_coalesced(request()));
+
+    assert_eq!(first.unwrap().value.get(), 42);
+    assert_eq!(second.unwrap().value.get(), 42);
+    assert_eq!(server.call_count("getBalance"), 1);
+    let stats = client.coalescing_stats();
+    assert_eq!((stats.calls, stats.coalesced), (2, 1));
+}
+
+#[tokio::test]
+async fn sending_call_gets_the_original_error() {
+    let server = slow_server().await;
+    let client = server.client();
+    server.inject_fault_times("getBalance", Fault::rpc(-32005, "Node is behind"), 1);
+
+    let (leader, follower) = tokio::join!(client.call_coalesced(request()), client.call_coalesced(request()));
+
+    assert!(matches!(leader, Err(JsonRpcError::Rpc { code: -32005, .. })));
+    let follower = follower.unwrap_err();
+    assert!(matches!(follower, JsonRpcError::Shared(_)));
+    assert!(matches!(follower.root(), JsonRpcError::Rpc { code: -32005, .. }));
+    assert_eq!(follower.kind(), "rpc");
+}
+
+#[tokio::test]
+async fn uncopyable_error_is_not_shared_without_followers() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    server.respond_with("getBalance", |_| Ok(json!({ "context": { "slot": 1 }, "value": "many" })));
+
+    let err = client.call_coalesced(request()).await.unwrap_err();
+    assert!(matches!(err, JsonRpcError::Deserialization(_)), "{:?}", err);
+}
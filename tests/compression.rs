@@ -0,0 +1,75 @@
+use base64::prelude::{Engine, BASE64_STANDARD};
+use serde_json::json;
+use solana_jsonrpc_client::methods::account::{AccountInfoConfig, RpcGetAccountInfoRequest};
+use solana_jsonrpc_client::test_utils::MockServer;
+use solana_jsonrpc_client::types::account::MAX_ACCOUNT_DATA_LEN;
+use solana_jsonrpc_client::types::{AccountDataError, Encoding};
+
+const PUBKEY: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+// A few kilobytes of zstd that would inflate to more than an account can
+// hold.
+#[tokio::test]
+async fn rejects_zstd_account_data_larger_than_an_account() {
+    let server = MockServer::start().await;
+    let bomb = zstd::encode_all(&vec![0u8; MAX_ACCOUNT_DATA_LEN as usize + 1][..], 19).unwrap();
+    assert!(bomb.len() < 4096);
+    server.respond_with("getAccountInfo", move |_| {
+        Ok(json!({
+            "context": { "slot": 1 },
+            "value": {
+                "lamports": 1,
+                "data": [BASE64_STANDARD.encode(&bomb), "base64+zstd"],
+                "owner": "owner",
+                "executable": false,
+                "rentEpoch": 0,
+            },
+        }))
+    });
+
+    let account = server
+        .client()
+        .call(RpcGetAccountInfoRequest {
+            pubkey: PUBKEY.to_string(),
+            config: Some(AccountInfoConfig {
+                encoding: Some(Encoding::Base64Zstd),
+                ..Default::default()
+            }),
+        })
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+
+    assert!(matches!(account.data_bytes(), Err(AccountDataError::TooLarge)));
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn decompresses_zstd_encoded_responses() {
+    use solana_jsonrpc_client::methods::chain::RpcGetGenesisHashRequest;
+    use solana_jsonrpc_client::JsonRpcClient;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = vec![0; 4096];
+        let len = socket.read(&mut request).await.unwrap();
+        let request = String::from_utf8_lossy(&request[..len]).to_lowercase();
+        assert!(request.contains("accept-encoding"), "{}", request);
+
+        let body = br#"{"jsonrpc":"2.0","result":"EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG","id":1}"#;
+        let body = zstd::encode_all(&body[..], 0).unwrap();
+        let head = format!(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-encoding: zstd\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+            body.len()
+        );
+        socket.write_all(head.as_bytes()).await.unwrap();
+        socket.write_all(&body).await.unwrap();
+    });
+
+    let hash = JsonRpcClient::connect(url).call(RpcGetGenesisHashRequest).await.unwrap();
+    assert_eq!(hash.value.to_string(), "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG");
+}
@@ -0,0 +1,45 @@
+use solana_jsonrpc_client::methods::transaction::RpcGetSignatureStatusesRequest;
+use solana_jsonrpc_client::test_utils::{MockServer, MAX_LOCKOUT_HISTORY};
+use solana_jsonrpc_client::types::{Commitment, Signature, TransactionConfirmationStatus};
+
+async fn confirmation_status(server: &MockServer, signature: Signature) -> Option<TransactionConfirmationStatus> {
+    let statuses = server
+        .client()
+        .call(RpcGetSignatureStatusesRequest {
+            signatures: vec![signature],
+            config: None,
+        })
+        .await
+        .unwrap()
+        .into_value();
+    let status = statuses[0].as_ref().unwrap();
+    assert_eq!(
+        status.satisfies(Commitment::Finalized),
+        status.confirmation_status == Some(TransactionConfirmationStatus::Finalized)
+    );
+    status.confirmation_status
+}
+
+#[tokio::test]
+async fn statuses_settle_as_slots_pass() {
+    let server = MockServer::start().await;
+    let signature = Signature::new([7; 64]);
+    server.ledger().land_transaction(signature.to_string());
+
+    assert_eq!(
+        confirmation_status(&server, signature).await,
+        Some(TransactionConfirmationStatus::Processed)
+    );
+
+    server.ledger().advance_slots(1);
+    assert_eq!(
+        confirmation_status(&server, signature).await,
+        Some(TransactionConfirmationStatus::Confirmed)
+    );
+
+    server.ledger().advance_slots(MAX_LOCKOUT_HISTORY);
+    assert_eq!(
+        confirmation_status(&server, signature).await,
+        Some(TransactionConfirmationStatus::Finalized)
+    );
+}
@@ -0,0 +1,73 @@
+use solana_jsonrpc_client::methods::chain::RpcGetGenesisHashRequest;
+use solana_jsonrpc_client::test_utils::MockServer;
+use solana_jsonrpc_client::{CallOptions, CancellationToken, JsonRpcError, RateLimit, RateLimiter};
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn times_out_after_the_deadline() {
+    let server = MockServer::start().await;
+    server.set_latency(Duration::from_millis(500));
+
+    let started = Instant::now();
+    let options = CallOptions::new().with_timeout(Duration::from_millis(50));
+    let err = server
+        .client()
+        .call_with_options(RpcGetGenesisHashRequest, options)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, JsonRpcError::Timeout { ref method, .. } if method == "getGenesisHash"));
+    assert!(started.elapsed() < Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn deadline_covers_rate_limiter_waits() {
+    let server = MockServer::start().await;
+    let limiter = RateLimiter::new().with_rate(RateLimit::per_second(NonZeroU32::new(1).unwrap()));
+    let client = server.client().with_rate_limiter(limiter);
+    client.call(RpcGetGenesisHashRequest).await.unwrap();
+
+    let options = CallOptions::new().with_timeout(Duration::from_millis(50));
+    let err = client
+        .call_with_options(RpcGetGenesisHashRequest, options)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, JsonRpcError::Timeout { .. }));
+    assert_eq!(server.call_count("getGenesisHash"), 1);
+}
+
+#[tokio::test]
+async fn cancels_when_the_token_fires() {
+    let server = MockServer::start().await;
+    server.set_latency(Duration::from_millis(500));
+    let cancel = CancellationToken::new();
+
+    let canceller = cancel.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        canceller.cancel();
+    });
+    let options = CallOptions::new().with_cancellation(cancel);
+    let err = server
+        .client()
+        .call_with_options(RpcGetGenesisHashRequest, options)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, JsonRpcError::Cancelled { .. }));
+}
+
+#[tokio::test]
+async fn completes_within_the_deadline() {
+    let server = MockServer::start().await;
+    let options = CallOptions::new().with_timeout(Duration::from_secs(5));
+
+    let hash = server
+        .client()
+        .call_with_options(RpcGetGenesisHashRequest, options)
+        .await
+        .unwrap();
+    assert_eq!(hash.value.to_string(), server.ledger().genesis_hash);
+}
@@ -0,0 +1,87 @@
+use solana_jsonrpc_client::deserialize::{AccountDeserialize, AccountDeserializeError};
+use solana_jsonrpc_client::filter::MAX_FILTERS;
+use solana_jsonrpc_client::methods::program::ProgramAccountFilter;
+use solana_jsonrpc_client::test_utils::{MockAccount, MockServer};
+use solana_jsonrpc_client::JsonRpcError;
+
+const PROGRAM: &str = "Counter111111111111111111111111111111111111";
+
+#[derive(Debug, PartialEq)]
+struct Counter(u64);
+
+impl AccountDeserialize for Counter {
+    const DISCRIMINATOR: &'static [u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+    fn deserialize_account(data: &[u8]) -> Result<Self, AccountDeserializeError> {
+        let bytes = data.get(..8).ok_or(AccountDeserializeError::Size {
+            expected: 8,
+            actual: data.len(),
+        })?;
+        Ok(Counter(u64::from_le_bytes(bytes.try_into().unwrap())))
+    }
+}
+
+fn counter(discriminator: &[u8], count: u64) -> MockAccount {
+    MockAccount::system(1_000_000).with_data(PROGRAM, [discriminator, &count.to_le_bytes()].concat())
+}
+
+#[tokio::test]
+async fn get_account_as_decodes_or_reports_the_account() {
+    let server = MockServer::start().await;
+    {
+        let mut ledger = server.ledger();
+        ledger.set_account("counter", counter(Counter::DISCRIMINATOR, 5));
+        ledger.set_account("other", counter(&[0; 8], 5));
+    }
+    let client = server.client();
+
+    assert_eq!(client.get_account_as::<Counter>("counter").await.unwrap(), Some(Counter(5)));
+    assert_eq!(client.get_account_as::<Counter>("missing").await.unwrap(), None);
+
+    match client.get_account_as::<Counter>("other").await {
+        Err(JsonRpcError::AccountDecode { pubkey, source }) => {
+            assert_eq!(pubkey, "other");
+            assert!(matches!(source, AccountDeserializeError::Discriminator { .. }));
+        }
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn get_program_accounts_as_filters_by_discriminator() {
+    let server = MockServer::start().await;
+    {
+        let mut ledger = server.ledger();
+        ledger.set_account("a", counter(Counter::DISCRIMINATOR, 1));
+        ledger.set_account("b", counter(Counter::DISCRIMINATOR, 2));
+        ledger.set_account("other", counter(&[0; 8], 3));
+    }
+
+    let mut accounts = server
+        .client()
+        .get_program_accounts_as::<Counter>(PROGRAM, vec![])
+        .await
+        .unwrap();
+    accounts.sort_by(|a, b| a.0.cmp(&b.0));
+
+    assert_eq!(
+        accounts,
+        vec![("a".to_string(), Counter(1)), ("b".to_string(), Counter(2))]
+    );
+    let params = &server.calls()[0].params;
+    assert!(params[1]["filters"][0]["memcmp"].is_object());
+}
+
+#[tokio::test]
+async fn the_discriminator_filter_counts_towards_the_limit() {
+    let server = MockServer::start().await;
+    let filters = vec![ProgramAccountFilter::DataSize(16); MAX_FILTERS];
+
+    let result = server
+        .client()
+        .get_program_accounts_as::<Counter>(PROGRAM, filters)
+        .await;
+
+    assert!(matches!(result, Err(JsonRpcError::InvalidFilter(_))));
+    assert_eq!(server.call_count("getProgramAccounts"), 0);
+}
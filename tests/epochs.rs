@@ -0,0 +1,25 @@
+use solana_jsonrpc_client::methods::epoch::{RpcGetEpochInfoRequest, RpcGetEpochScheduleRequest};
+use solana_jsonrpc_client::test_utils::{Ledger, MockServer};
+use std::num::NonZeroU64;
+
+#[tokio::test]
+async fn the_schedule_agrees_with_epoch_info() {
+    let mut ledger = Ledger::default().with_slots_per_epoch(NonZeroU64::new(100).unwrap());
+    ledger.advance_slots(1234 - ledger.slot);
+    let server = MockServer::with_ledger(ledger).await;
+    let client = server.client();
+
+    let schedule = client.call(RpcGetEpochScheduleRequest).await.unwrap().into_value();
+    let info = client
+        .call(RpcGetEpochInfoRequest {
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap()
+        .into_value();
+
+    assert_eq!(schedule.epoch_and_slot_index(info.absolute_slot), (info.epoch, info.slot_index));
+    assert_eq!(schedule.slots_in_epoch(info.epoch), info.slots_in_epoch);
+    assert_eq!(schedule.first_slot_in_epoch(info.epoch).get(), 1200);
+}
@@ -0,0 +1,58 @@
+use solana_jsonrpc_client::methods::program::{ProgramAccountsConfig, RpcGetProgramAccountsRequest};
+use solana_jsonrpc_client::test_utils::{MockAccount, MockServer};
+use solana_jsonrpc_client::types::Encoding;
+use solana_jsonrpc_client::Filters;
+
+const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const OWNER: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+const OTHER: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+// An initialized token account, with room for extensions past the base
+// 165 bytes.
+fn token_account(owner: &str, len: usize) -> MockAccount {
+    let mut data = vec![0; len];
+    data[32..64].copy_from_slice(&bs58::decode(owner).into_vec().unwrap());
+    data[108] = 1;
+    if len > 165 {
+        data[165] = 2;
+    }
+    MockAccount::system(2_039_280).with_data(TOKEN_2022_PROGRAM, data)
+}
+
+async fn matching(server: &MockServer, filters: Filters) -> Vec<String> {
+    let mut pubkeys: Vec<_> = server
+        .client()
+        .call(RpcGetProgramAccountsRequest {
+            program_id: TOKEN_2022_PROGRAM.to_string(),
+            config: Some(ProgramAccountsConfig {
+                encoding: Some(Encoding::Base64),
+                filters: Some(filters.build().unwrap()),
+                ..Default::default()
+            }),
+        })
+        .await
+        .unwrap()
+        .into_value()
+        .into_iter()
+        .map(|account| account.pubkey)
+        .collect();
+    pubkeys.sort();
+    pubkeys
+}
+
+#[tokio::test]
+async fn presets_skip_token_2022_accounts_with_extensions() {
+    let server = MockServer::start().await;
+    {
+        let mut ledger = server.ledger();
+        ledger.set_account("base", token_account(OWNER, 165));
+        ledger.set_account("extended", token_account(OWNER, 170));
+        ledger.set_account("other", token_account(OTHER, 165));
+    }
+
+    assert_eq!(matching(&server, Filters::token_accounts_for_owner(OWNER)).await, ["base"]);
+    assert_eq!(
+        matching(&server, Filters::new().token_account_state().memcmp_pubkey(32, OWNER)).await,
+        ["base", "extended"]
+    );
+}
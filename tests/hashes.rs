@@ -0,0 +1,51 @@
+use serde_json::json;
+use solana_jsonrpc_client::methods::block::{RpcGetLatestBlockhashRequest, RpcIsBlockhashValidRequest};
+use solana_jsonrpc_client::test_utils::MockServer;
+use solana_jsonrpc_client::JsonRpcError;
+
+#[tokio::test]
+async fn blockhashes_round_trip_as_hashes() {
+    let server = MockServer::start().await;
+    let client = server.client();
+
+    let latest = client
+        .call(RpcGetLatestBlockhashRequest {
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap()
+        .into_value();
+    assert_eq!(latest.blockhash.to_string(), server.ledger().latest_blockhash());
+
+    let valid = client
+        .call(RpcIsBlockhashValidRequest {
+            blockhash: latest.blockhash,
+            config: None,
+        })
+        .await
+        .unwrap()
+        .into_value();
+    assert!(valid);
+}
+
+#[tokio::test]
+async fn malformed_blockhashes_fail_to_decode() {
+    let server = MockServer::start().await;
+    server.respond_with("getLatestBlockhash", |_| {
+        Ok(json!({
+            "context": { "slot": 1 },
+            "value": { "blockhash": "not-base58", "lastValidBlockHeight": 150 },
+        }))
+    });
+
+    let result = server
+        .client()
+        .call(RpcGetLatestBlockhashRequest {
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await;
+
+    assert!(matches!(result, Err(JsonRpcError::Deserialization(_))));
+}
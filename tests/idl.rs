@@ -0,0 +1,41 @@
+#![cfg(feature = "idl")]
+
+use flate2::write::ZlibEncoder;
+use solana_jsonrpc_client::anchor::account_discriminator;
+use solana_jsonrpc_client::anchor::idl::idl_address;
+use solana_jsonrpc_client::test_utils::{MockAccount, MockServer};
+use std::io::Write;
+
+const PROGRAM: &str = "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS";
+
+fn idl_account(json: &str) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(json.as_bytes()).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let mut data = account_discriminator("IdlAccount").to_vec();
+    data.extend([0; 32]);
+    data.extend((compressed.len() as u32).to_le_bytes());
+    data.extend(compressed);
+    data
+}
+
+#[tokio::test]
+async fn fetches_the_published_idl() {
+    let server = MockServer::start().await;
+    let data = idl_account(r#"{"address":"Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS","metadata":{"name":"vault","version":"0.1.0"}}"#);
+    server
+        .ledger()
+        .set_account(idl_address(PROGRAM).unwrap(), MockAccount::system(1).with_data(PROGRAM, data));
+
+    let idl = server.client().get_idl(PROGRAM).await.unwrap().unwrap();
+
+    assert_eq!(idl.name(), Some("vault"));
+    assert_eq!(idl.address.as_deref(), Some(PROGRAM));
+}
+
+#[tokio::test]
+async fn programs_without_an_idl_return_none() {
+    let server = MockServer::start().await;
+    assert!(server.client().get_idl(PROGRAM).await.unwrap().is_none());
+}
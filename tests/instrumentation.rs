@@ -0,0 +1,128 @@
+#![cfg(feature = "instrumentation")]
+
+use futures::StreamExt;
+use metrics_util::debugging::{DebugValue, DebuggingRecorder, Snapshotter};
+use solana_jsonrpc_client::methods::account::RpcGetBalanceRequest;
+use solana_jsonrpc_client::methods::chain::RpcGetGenesisHashRequest;
+use solana_jsonrpc_client::methods::program::RpcGetProgramAccountsRequest;
+use solana_jsonrpc_client::test_utils::{Fault, MockAccount, MockServer};
+use solana_jsonrpc_client::{CallOptions, JsonRpcError, ResponseCache};
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+const PUBKEY: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+// Runs `test` with a recorder installed for this thread only.
+fn with_metrics<F: Future<Output = ()>>(test: impl FnOnce() -> F) -> Snapshotter {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(test())
+    });
+    snapshotter
+}
+
+fn counter(snapshotter: &Snapshotter, name: &str, labels: &[(&str, &str)]) -> u64 {
+    snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .filter(|(key, _, _, _)| {
+            let key = key.key();
+            key.name() == name
+                && labels
+                    .iter()
+                    .all(|(label, value)| key.labels().any(|l| l.key() == *label && l.value() == *value))
+        })
+        .map(|(_, _, _, value)| match value {
+            DebugValue::Counter(count) => count,
+            _ => 0,
+        })
+        .sum()
+}
+
+fn balance_request() -> RpcGetBalanceRequest {
+    RpcGetBalanceRequest {
+        pubkey: PUBKEY.to_string(),
+        config: None,
+    }
+}
+
+#[test]
+fn records_timeouts_as_errors() {
+    let snapshotter = with_metrics(|| async {
+        let server = MockServer::start().await;
+        server.set_latency(Duration::from_millis(200));
+        let options = CallOptions::new().with_timeout(Duration::from_millis(20));
+
+        let result = server.client().call_with_options(balance_request(), options).await;
+        assert!(matches!(result, Err(JsonRpcError::Timeout { .. })));
+    });
+
+    let labels = [("method", "getBalance"), ("kind", "timeout")];
+    assert_eq!(counter(&snapshotter, "solana_rpc_errors_total", &labels), 1);
+    assert_eq!(counter(&snapshotter, "solana_rpc_requests_total", &[("method", "getBalance")]), 1);
+}
+
+#[test]
+fn records_rpc_error_codes() {
+    let snapshotter = with_metrics(|| async {
+        let server = MockServer::start().await;
+        server.inject_fault("getBalance", Fault::rpc(-32005, "Node is behind"));
+        assert!(server.client().call(balance_request()).await.is_err());
+    });
+
+    let labels = [("kind", "rpc"), ("code", "-32005")];
+    assert_eq!(counter(&snapshotter, "solana_rpc_errors_total", &labels), 1);
+}
+
+#[test]
+fn cache_hits_send_no_request() {
+    let snapshotter = with_metrics(|| async {
+        let server = MockServer::start().await;
+        let client = server
+            .client()
+            .with_cache(ResponseCache::lru(NonZeroUsize::new(8).unwrap()));
+        client.call(RpcGetGenesisHashRequest).await.unwrap();
+        client.call(RpcGetGenesisHashRequest).await.unwrap();
+    });
+
+    let labels = [("method", "getGenesisHash")];
+    assert_eq!(counter(&snapshotter, "solana_rpc_requests_total", &labels), 1);
+}
+
+#[test]
+fn instruments_streamed_program_accounts() {
+    let snapshotter = with_metrics(|| async {
+        let server = MockServer::start().await;
+        let program = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+        for i in 0..3 {
+            server
+                .ledger()
+                .set_account(format!("account-{}", i), MockAccount::system(1).with_data(program, vec![0; 8]));
+        }
+
+        let request = RpcGetProgramAccountsRequest {
+            program_id: program.to_string(),
+            config: None,
+        };
+        let accounts: Vec<_> = server
+            .client()
+            .stream_program_accounts(request)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(accounts.len(), 3);
+    });
+
+    let labels = [("method", "getProgramAccounts")];
+    assert_eq!(counter(&snapshotter, "solana_rpc_requests_total", &labels), 1);
+    assert!(counter(&snapshotter, "solana_rpc_response_bytes_total", &labels) > 0);
+    assert_eq!(counter(&snapshotter, "solana_rpc_errors_total", &labels), 0);
+}
@@ -0,0 +1,103 @@
+use serde_json::{json, Value};
+use solana_jsonrpc_client::loader::{MAX_CONCURRENT_CHUNKS, MAX_MULTIPLE_ACCOUNTS};
+use solana_jsonrpc_client::test_utils::MockServer;
+use solana_jsonrpc_client::{AccountLoader, JsonRpcError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+fn pubkeys(count: usize) -> Vec<String> {
+    (0..count).map(|i| format!("account-{}", i)).collect()
+}
+
+#[tokio::test]
+async fn get_multiple_accounts_preserves_order_across_chunks() {
+    let server = MockServer::start().await;
+    let pubkeys = pubkeys(250);
+    for (i, pubkey) in pubkeys.iter().enumerate().step_by(2) {
+        server.ledger().set_balance(pubkey.clone(), i as u64 + 1);
+    }
+
+    let accounts = server.client().get_multiple_accounts(&pubkeys, None).await.unwrap();
+
+    assert_eq!(accounts.len(), 250);
+    for (i, account) in accounts.iter().enumerate() {
+        match account {
+            Some(account) => assert_eq!(account.lamports.get(), i as u64 + 1),
+            None => assert_eq!(i % 2, 1),
+        }
+    }
+    assert_eq!(server.call_count("getMultipleAccounts"), 3);
+}
+
+// Handlers run on the server's connection tasks, so with enough worker
+// threads every call in flight is inside the handler at the same time.
+#[tokio::test(flavor = "multi_thread", worker_threads = 16)]
+async fn get_multiple_accounts_bounds_concurrent_chunks() {
+    let server = MockServer::start().await;
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    {
+        let (in_flight, peak) = (in_flight.clone(), peak.clone());
+        server.respond_with("getMultipleAccounts", move |params| {
+            let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            peak.fetch_max(current, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(50));
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+
+            let len = params[0].as_array().map_or(0, Vec::len);
+            Ok(json!({ "context": { "slot": 1 }, "value": vec![Value::Null; len] }))
+        });
+    }
+    let pubkeys = pubkeys(MAX_MULTIPLE_ACCOUNTS * MAX_CONCURRENT_CHUNKS * 2);
+
+    let accounts = server.client().get_multiple_accounts(&pubkeys, None).await.unwrap();
+
+    assert_eq!(accounts.len(), pubkeys.len());
+    assert_eq!(server.call_count("getMultipleAccounts"), MAX_CONCURRENT_CHUNKS * 2);
+    assert_eq!(peak.load(Ordering::SeqCst), MAX_CONCURRENT_CHUNKS);
+}
+
+#[tokio::test]
+async fn get_multiple_accounts_rejects_short_responses() {
+    let server = MockServer::start().await;
+    server.respond_with("getMultipleAccounts", |_| {
+        Ok(json!({ "context": { "slot": 1 }, "value": [null] }))
+    });
+
+    let err = server.client().get_multiple_accounts(&pubkeys(2), None).await.unwrap_err();
+
+    match err {
+        JsonRpcError::InvalidResponse(message) => assert_eq!(message, "expected 2 accounts, got 1"),
+        other => panic!("unexpected error {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn loader_batches_loads_into_one_call() {
+    let server = MockServer::start().await;
+    server.ledger().set_balance("a", 1);
+    let loader = AccountLoader::new(server.client());
+
+    let (a, b) = tokio::join!(loader.load("a"), loader.load("b"));
+
+    assert_eq!(a.unwrap().unwrap().lamports.get(), 1);
+    assert!(b.unwrap().is_none());
+    assert_eq!(server.call_count("getMultipleAccounts"), 1);
+}
+
+#[tokio::test]
+async fn loader_fails_every_waiter_on_short_response() {
+    let server = MockServer::start().await;
+    server.respond_with("getMultipleAccounts", |_| {
+        Ok(json!({ "context": { "slot": 1 }, "value": [null] }))
+    });
+    let loader = AccountLoader::new(server.client());
+
+    let (a, b) = tokio::join!(loader.load("a"), loader.load("b"));
+
+    for result in [a, b] {
+        let err = result.unwrap_err();
+        assert!(matches!(err.root(), JsonRpcError::InvalidResponse(_)), "{:?}", err);
+    }
+}
@@ -0,0 +1,530 @@
+use solana_jsonrpc_client::methods::account::RpcGetLargestAccountsRequest;
+use solana_jsonrpc_client::methods::block::{
+    BlockConfig, RpcGetBlockRequest, RpcGetBlocksRequest, RpcGetLatestBlockhashRequest, RpcGetSlotLeaderRequest,
+    TransactionDetails,
+};
+use solana_jsonrpc_client::methods::chain::{
+    RpcGetClusterNodesRequest, RpcGetIdentityRequest, RpcGetMaxRetransmitSlotRequest,
+    RpcGetMaxShredInsertSlotRequest, RpcGetSupplyRequest,
+};
+use solana_jsonrpc_client::methods::epoch::{RpcGetEpochInfoRequest, RpcGetLeaderScheduleRequest};
+use solana_jsonrpc_client::methods::inflation::{
+    RpcGetInflationGovernorRequest, RpcGetInflationRateRequest, RpcGetInflationRewardRequest,
+};
+use solana_jsonrpc_client::methods::program::{
+    MemcmpFilter, ProgramAccountFilter, ProgramAccountsConfig, RpcGetProgramAccountsRequest,
+};
+use solana_jsonrpc_client::methods::stake::RpcGetStakeActivationRequest;
+use solana_jsonrpc_client::methods::token::{
+    RpcGetTokenAccountBalanceRequest, RpcGetTokenAccountsByDelegateRequest, RpcGetTokenAccountsByOwnerRequest,
+    RpcGetTokenLargestAccountsRequest, RpcGetTokenSupplyRequest, TokenAccountsConfig,
+};
+use solana_jsonrpc_client::methods::transaction::{RpcGetRecentPrioritizationFeesRequest, RpcGetTransactionRequest};
+use solana_jsonrpc_client::methods::vote::RpcGetVoteAccountsRequest;
+use solana_jsonrpc_client::test_utils::{Fault, Ledger, MockAccount, MockServer, MockSignatureStatus};
+use solana_jsonrpc_client::types::{Encoding, Lamports, Signature};
+use solana_jsonrpc_client::JsonRpcError;
+use std::num::NonZeroU64;
+
+const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const STAKE_PROGRAM_ID: &str = "Stake11111111111111111111111111111111111111";
+
+fn pubkey(seed: u8) -> String {
+    bs58::encode([seed; 32]).into_string()
+}
+
+fn signature(seed: u8) -> Signature {
+    Signature::new([seed; 64])
+}
+
+fn mint_data(supply: u64, decimals: u8) -> Vec<u8> {
+    let mut data = vec![0; 82];
+    data[36..44].copy_from_slice(&supply.to_le_bytes());
+    data[44] = decimals;
+    data[45] = 1;
+    data
+}
+
+fn token_account_data(mint: u8, owner: u8, amount: u64, delegate: Option<u8>) -> Vec<u8> {
+    let mut data = vec![0; 165];
+    data[..32].copy_from_slice(&[mint; 32]);
+    data[32..64].copy_from_slice(&[owner; 32]);
+    data[64..72].copy_from_slice(&amount.to_le_bytes());
+    if let Some(delegate) = delegate {
+        data[72] = 1;
+        data[76..108].copy_from_slice(&[delegate; 32]);
+    }
+    data[108] = 1;
+    data
+}
+
+fn token_accounts_config(mint: Option<String>) -> TokenAccountsConfig {
+    TokenAccountsConfig {
+        commitment: None,
+        encoding: Some(Encoding::Base64),
+        mint,
+        program_id: None,
+        min_context_slot: None,
+    }
+}
+
+#[tokio::test]
+async fn injected_fault_expires_after_the_given_number_of_calls() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    server.inject_fault_times("getIdentity", Fault::rpc(-32005, "Node is behind"), 2);
+
+    for _ in 0..2 {
+        let err = client.call(RpcGetIdentityRequest).await.unwrap_err();
+        assert!(matches!(err, JsonRpcError::Rpc { code: -32005, .. }));
+    }
+    client.call(RpcGetIdentityRequest).await.unwrap();
+}
+
+#[tokio::test]
+async fn injecting_a_fault_zero_times_clears_it() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    server.inject_fault("getIdentity", Fault::Http(503));
+    server.inject_fault_times("getIdentity", Fault::Http(503), 0);
+
+    client.call(RpcGetIdentityRequest).await.unwrap();
+    client.call(RpcGetIdentityRequest).await.unwrap();
+}
+
+#[tokio::test]
+async fn epoch_follows_configured_slots_per_epoch() {
+    let mut ledger = Ledger::default().with_slots_per_epoch(NonZeroU64::new(32).unwrap());
+    ledger.advance_slots(70);
+    let server = MockServer::with_ledger(ledger).await;
+
+    let info = server
+        .client()
+        .call(RpcGetEpochInfoRequest {
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(info.epoch.get(), 2);
+    assert_eq!(info.slot_index, 6);
+    assert_eq!(info.slots_in_epoch, 32);
+}
+
+#[tokio::test]
+async fn get_block_serves_landed_transactions_and_fee_rewards() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    server.ledger().advance_slots(5);
+    server.ledger().land_transaction(signature(1).to_string());
+    server.ledger().land_transaction(signature(2).to_string());
+    server.ledger().set_signature_status(
+        signature(3).to_string(),
+        MockSignatureStatus {
+            slot: 5,
+            err: Some("AccountNotFound".into()),
+        },
+    );
+    server.ledger().advance_slots(1);
+
+    let latest = client
+        .call(RpcGetLatestBlockhashRequest {
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap()
+        .value;
+    let block = client
+        .call(RpcGetBlockRequest { slot: 5, config: None })
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+    let next = client
+        .call(RpcGetBlockRequest { slot: 6, config: None })
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+
+    assert_eq!(block.parent_slot, Some(4));
+    assert_eq!(block.block_height, Some(5));
+    assert_eq!(next.blockhash, Some(latest.blockhash));
+    assert_eq!(next.previous_blockhash, block.blockhash);
+    let transactions = block.transactions.as_ref().unwrap();
+    assert_eq!(transactions.len(), 3);
+    assert_eq!(transactions.iter().filter(|tx| tx.meta.as_ref().unwrap().err.is_some()).count(), 1);
+    assert_eq!(block.total_fees(), Some(Lamports(15_000)));
+    assert_eq!(block.leader_fees(), Some(Lamports(7_500)));
+    assert_eq!(block.burned_fees(), Some(Lamports(7_500)));
+    assert_eq!(next.total_fees(), Some(Lamports::ZERO));
+}
+
+#[tokio::test]
+async fn get_block_honours_transaction_details_and_rewards() {
+    let server = MockServer::start().await;
+    server.ledger().land_transaction(signature(1).to_string());
+
+    let block = server
+        .client()
+        .call(RpcGetBlockRequest {
+            slot: 0,
+            config: Some(BlockConfig {
+                encoding: None,
+                transaction_details: Some(TransactionDetails::None),
+                rewards: Some(false),
+                commitment: None,
+                max_supported_transaction_version: Some(0),
+            }),
+        })
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+
+    assert!(block.transactions.is_none());
+    assert!(block.rewards.is_none());
+}
+
+#[tokio::test]
+async fn get_block_rejects_future_slots() {
+    let server = MockServer::start().await;
+
+    let err = server
+        .client()
+        .call(RpcGetBlockRequest { slot: 1, config: None })
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, JsonRpcError::Rpc { code: -32004, .. }));
+}
+
+#[tokio::test]
+async fn get_blocks_stops_at_the_current_slot() {
+    let server = MockServer::start().await;
+    server.ledger().advance_slots(10);
+
+    let blocks = server
+        .client()
+        .call(RpcGetBlocksRequest {
+            start_slot: 8,
+            end_slot: Some(20),
+            commitment: None,
+        })
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(blocks, vec![8, 9, 10]);
+}
+
+#[tokio::test]
+async fn get_transaction_finds_landed_signatures() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    server.ledger().advance_slots(3);
+    server.ledger().land_transaction(signature(1).to_string());
+
+    let transaction = client
+        .call(RpcGetTransactionRequest {
+            signature: signature(1),
+            config: None,
+        })
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+    let missing = client
+        .call(RpcGetTransactionRequest {
+            signature: signature(2),
+            config: None,
+        })
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(transaction.slot, Some(3));
+    assert_eq!(transaction.meta.unwrap().fee, Lamports(5000));
+    assert!(missing.is_none());
+}
+
+#[tokio::test]
+async fn serves_the_identity_as_the_only_validator() {
+    let ledger = Ledger::default().with_slots_per_epoch(NonZeroU64::new(8).unwrap());
+    let identity = ledger.identity.clone();
+    let vote_account = ledger.vote_account.clone();
+    let server = MockServer::with_ledger(ledger).await;
+    let client = server.client();
+    server
+        .ledger()
+        .set_account(pubkey(1), MockAccount::system(3_000).with_data(STAKE_PROGRAM_ID, vec![0; 200]));
+    server.ledger().advance_slots(40);
+
+    let nodes = client.call(RpcGetClusterNodesRequest).await.unwrap().value;
+    let leader = client
+        .call(RpcGetSlotLeaderRequest {
+            slot: None,
+            commitment: None,
+        })
+        .await
+        .unwrap()
+        .value;
+    let schedule = client
+        .call(RpcGetLeaderScheduleRequest { slot: None, config: None })
+        .await
+        .unwrap()
+        .value
+        .unwrap();
+    let far_schedule = client
+        .call(RpcGetLeaderScheduleRequest {
+            slot: Some(1_000),
+            config: None,
+        })
+        .await
+        .unwrap()
+        .value;
+    let votes = client.call(RpcGetVoteAccountsRequest { config: None }).await.unwrap().value;
+    let stake = client
+        .call(RpcGetStakeActivationRequest {
+            pubkey: pubkey(1),
+            config: None,
+        })
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(nodes.len(), 1);
+    assert_eq!(nodes[0].pubkey, identity);
+    assert_eq!(leader, identity);
+    assert_eq!(schedule[&identity], (0..8).collect::<Vec<_>>());
+    assert!(far_schedule.is_none());
+    assert_eq!(votes.current.len(), 1);
+    assert_eq!(votes.current[0].vote_pubkey, vote_account);
+    assert_eq!(votes.current[0].activated_stake, Lamports(3_000));
+    assert_eq!(votes.current[0].root_slot, Some(8));
+    assert!(votes.delinquent.is_empty());
+    assert_eq!(stake.state, "active");
+    assert_eq!(stake.active, Lamports(3_000));
+}
+
+#[tokio::test]
+async fn get_stake_activation_rejects_other_accounts() {
+    let server = MockServer::start().await;
+    server.ledger().set_balance(pubkey(1), 1);
+
+    for pubkey in [pubkey(1), pubkey(2)] {
+        let err = server
+            .client()
+            .call(RpcGetStakeActivationRequest { pubkey, config: None })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, JsonRpcError::Rpc { code: -32602, .. }));
+    }
+}
+
+#[tokio::test]
+async fn serves_slot_and_fee_methods_from_the_ledger() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    server.ledger().advance_slots(200);
+
+    let retransmit = client.call(RpcGetMaxRetransmitSlotRequest).await.unwrap().value;
+    let shred_insert = client.call(RpcGetMaxShredInsertSlotRequest).await.unwrap().value;
+    let fees = client
+        .call(RpcGetRecentPrioritizationFeesRequest { addresses: None })
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(retransmit, 200);
+    assert_eq!(shred_insert, 200);
+    assert_eq!(fees.len(), 150);
+    assert_eq!(fees.first().unwrap().slot, 51);
+    assert_eq!(fees.last().unwrap().slot, 200);
+    assert!(fees.iter().all(|fee| fee.prioritization_fee == 0));
+}
+
+#[tokio::test]
+async fn serves_inflation_without_rewards() {
+    let server = MockServer::start().await;
+    let client = server.client();
+
+    let governor = client
+        .call(RpcGetInflationGovernorRequest { commitment: None })
+        .await
+        .unwrap()
+        .value;
+    let rate = client.call(RpcGetInflationRateRequest).await.unwrap().value;
+    let rewards = client
+        .call(RpcGetInflationRewardRequest {
+            addresses: vec![pubkey(1), pubkey(2)],
+            config: None,
+        })
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(rate.total, governor.initial);
+    assert!((rate.validator + rate.foundation - rate.total).abs() < f64::EPSILON);
+    assert_eq!(rate.epoch, 0);
+    assert_eq!(rewards.len(), 2);
+    assert!(rewards.iter().all(Option::is_none));
+}
+
+#[tokio::test]
+async fn supply_and_largest_accounts_sum_the_ledger() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    for seed in 1..=25 {
+        server.ledger().set_balance(pubkey(seed), seed as u64 * 100);
+    }
+
+    let supply = client
+        .call(RpcGetSupplyRequest {
+            commitment: None,
+            exclude_non_circulating_supply_list: None,
+        })
+        .await
+        .unwrap();
+    let largest = client
+        .call(RpcGetLargestAccountsRequest { config: None })
+        .await
+        .unwrap();
+
+    assert!(supply.context.is_some());
+    assert_eq!(supply.value.total, Lamports(32_500));
+    assert_eq!(supply.value.circulating, Lamports(32_500));
+    assert_eq!(supply.value.non_circulating_accounts, Some(Vec::new()));
+    assert_eq!(largest.value.len(), 20);
+    assert_eq!(largest.value[0].address, pubkey(25));
+    assert_eq!(largest.value[0].lamports, Lamports(2_500));
+    assert_eq!(largest.value[19].lamports, Lamports(600));
+}
+
+#[tokio::test]
+async fn serves_spl_token_accounts_and_mints() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    {
+        let mut ledger = server.ledger();
+        ledger.set_account(pubkey(1), MockAccount::system(1).with_data(TOKEN_PROGRAM_ID, mint_data(1_250_000, 6)));
+        let accounts = [(10, 2, 1_000_000, Some(4)), (11, 2, 250_000, None), (12, 3, 0, Some(4))];
+        for (address, owner, amount, delegate) in accounts {
+            ledger.set_account(
+                pubkey(address),
+                MockAccount::system(1).with_data(TOKEN_PROGRAM_ID, token_account_data(1, owner, amount, delegate)),
+            );
+        }
+    }
+
+    let balance = client
+        .call(RpcGetTokenAccountBalanceRequest {
+            account: pubkey(10),
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap()
+        .value;
+    let supply = client
+        .call(RpcGetTokenSupplyRequest {
+            mint: pubkey(1),
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap()
+        .value;
+    let by_owner = client
+        .call(RpcGetTokenAccountsByOwnerRequest {
+            owner: pubkey(2),
+            config: token_accounts_config(Some(pubkey(1))),
+        })
+        .await
+        .unwrap()
+        .value;
+    let by_delegate = client
+        .call(RpcGetTokenAccountsByDelegateRequest {
+            delegate: pubkey(4),
+            config: token_accounts_config(Some(pubkey(1))),
+        })
+        .await
+        .unwrap()
+        .value;
+    let largest = client
+        .call(RpcGetTokenLargestAccountsRequest {
+            mint: pubkey(1),
+            commitment: None,
+        })
+        .await
+        .unwrap()
+        .value;
+
+    assert_eq!(balance.amount, "1000000");
+    assert_eq!(balance.ui_amount_string, "1");
+    assert_eq!(supply.amount, "1250000");
+    assert_eq!(supply.ui_amount_string, "1.25");
+    let mut owned = by_owner.iter().map(|account| account.pubkey.clone()).collect::<Vec<_>>();
+    owned.sort();
+    let mut expected = vec![pubkey(10), pubkey(11)];
+    expected.sort();
+    assert_eq!(owned, expected);
+    assert_eq!(by_delegate.len(), 2);
+    let addresses = largest.iter().map(|balance| balance.address.clone()).collect::<Vec<_>>();
+    assert_eq!(addresses, vec![pubkey(10), pubkey(11), pubkey(12)]);
+    assert_eq!(largest[1].quantity().unwrap().to_string(), "0.25");
+}
+
+#[tokio::test]
+async fn token_methods_reject_accounts_that_are_not_token_accounts() {
+    let server = MockServer::start().await;
+    server.ledger().set_balance(pubkey(1), 1);
+
+    let err = server
+        .client()
+        .call(RpcGetTokenAccountBalanceRequest {
+            account: pubkey(1),
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap_err();
+    let no_mint = server
+        .client()
+        .call(RpcGetTokenSupplyRequest {
+            mint: pubkey(1),
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, JsonRpcError::Rpc { code: -32602, .. }));
+    assert!(matches!(no_mint, JsonRpcError::Rpc { code: -32602, .. }));
+}
+
+#[tokio::test]
+async fn get_program_accounts_rejects_memcmp_offsets_past_the_end_of_memory() {
+    let server = MockServer::start().await;
+    server
+        .ledger()
+        .set_account(pubkey(1), MockAccount::system(1).with_data(TOKEN_PROGRAM_ID, vec![0; 165]));
+    let request = |offset| RpcGetProgramAccountsRequest {
+        program_id: TOKEN_PROGRAM_ID.to_string(),
+        config: Some(ProgramAccountsConfig {
+            encoding: Some(Encoding::Base64),
+            filters: Some(vec![ProgramAccountFilter::Memcmp(MemcmpFilter::base58(offset, &[0]))]),
+            ..Default::default()
+        }),
+    };
+
+    let err = server.client().call(request(usize::MAX)).await.unwrap_err();
+    assert!(matches!(err, JsonRpcError::Rpc { code: -32602, .. }), "{:?}", err);
+
+    // The connection survived and later filters still apply.
+    let accounts = server.client().call(request(164)).await.unwrap().into_value();
+    assert_eq!(accounts.len(), 1);
+}
@@ -0,0 +1,95 @@
+use serde_json::json;
+use solana_jsonrpc_client::methods::token::{RpcGetTokenAccountsByOwnerRequest, TokenAccountsConfig};
+use solana_jsonrpc_client::test_utils::MockServer;
+use solana_jsonrpc_client::types::parsed::TokenAccountState;
+use solana_jsonrpc_client::types::{AccountDataError, Encoding};
+
+const OWNER: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+fn keyed_account(pubkey: &str, data: serde_json::Value) -> serde_json::Value {
+    json!({
+        "pubkey": pubkey,
+        "account": {
+            "lamports": 2039280,
+            "owner": TOKEN_PROGRAM,
+            "data": data,
+            "executable": false,
+            "rentEpoch": 18446744073709551615u64,
+            "space": 165,
+        },
+    })
+}
+
+#[tokio::test]
+async fn token_accounts_decode_from_json_parsed() {
+    let server = MockServer::start().await;
+    server.respond_with("getTokenAccountsByOwner", |_| {
+        Ok(json!({
+            "context": { "slot": 10 },
+            "value": [
+                keyed_account(
+                    "parsed",
+                    json!({
+                        "program": "spl-token",
+                        "parsed": {
+                            "type": "account",
+                            "info": {
+                                "isNative": false,
+                                "mint": MINT,
+                                "owner": OWNER,
+                                "state": "initialized",
+                                "tokenAmount": { "amount": "250", "decimals": 2, "uiAmount": 2.5, "uiAmountString": "2.5" },
+                            },
+                        },
+                        "space": 165,
+                    }),
+                ),
+                keyed_account("binary", json!(["", "base64"])),
+                keyed_account(
+                    "mint",
+                    json!({
+                        "program": "spl-token",
+                        "parsed": {
+                            "type": "mint",
+                            "info": {
+                                "decimals": 2,
+                                "freezeAuthority": null,
+                                "isInitialized": true,
+                                "mintAuthority": OWNER,
+                                "supply": "1000",
+                            },
+                        },
+                        "space": 82,
+                    }),
+                ),
+            ],
+        }))
+    });
+
+    let accounts = server
+        .client()
+        .call(RpcGetTokenAccountsByOwnerRequest {
+            owner: OWNER.to_string(),
+            config: TokenAccountsConfig {
+                commitment: None,
+                encoding: Some(Encoding::JsonParsed),
+                mint: Some(MINT.to_string()),
+                program_id: None,
+                min_context_slot: None,
+            },
+        })
+        .await
+        .unwrap()
+        .into_value();
+
+    let parsed = accounts[0].token_account().unwrap();
+    assert_eq!(parsed.mint, MINT);
+    assert_eq!(parsed.owner, OWNER);
+    assert_eq!(parsed.state, TokenAccountState::Initialized);
+    assert_eq!(parsed.token_amount.quantity().unwrap().to_string(), "2.5");
+
+    assert!(matches!(accounts[1].token_account(), Err(AccountDataError::NotParsed)));
+    assert!(matches!(accounts[2].token_account(), Err(AccountDataError::NotTokenAccount)));
+}
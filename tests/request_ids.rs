@@ -0,0 +1,20 @@
+use solana_jsonrpc_client::methods::chain::RpcGetGenesisHashRequest;
+use solana_jsonrpc_client::test_utils::MockServer;
+use solana_jsonrpc_client::RequestId;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+#[tokio::test]
+async fn custom_string_ids_round_trip() {
+    let server = MockServer::start().await;
+    let counter = Arc::new(AtomicU64::new(0));
+    let ids = counter.clone();
+    let client = server
+        .client()
+        .with_id_generator(move || RequestId::from(format!("req-{}", ids.fetch_add(1, Ordering::Relaxed))));
+
+    client.call(RpcGetGenesisHashRequest).await.unwrap();
+    client.call(RpcGetGenesisHashRequest).await.unwrap();
+
+    assert_eq!(counter.load(Ordering::Relaxed), 2);
+}
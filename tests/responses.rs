@@ -0,0 +1,93 @@
+use serde::Serialize;
+use serde_json::json;
+use solana_jsonrpc_client::client::RpcRequest;
+use solana_jsonrpc_client::methods::account::RpcGetBalanceRequest;
+use solana_jsonrpc_client::methods::block::RpcGetSlotRequest;
+use solana_jsonrpc_client::methods::program::{ProgramAccountsConfig, RpcGetProgramAccountsRequest};
+use solana_jsonrpc_client::test_utils::{MockAccount, MockServer};
+use solana_jsonrpc_client::types::Response;
+
+#[derive(Debug, Clone, Serialize)]
+struct GetFeeForMessageRequest(Vec<String>);
+
+impl RpcRequest for GetFeeForMessageRequest {
+    fn method_name() -> &'static str {
+        "getFeeForMessage"
+    }
+
+    type Response = Response<Option<u64>>;
+
+    fn has_context(&self) -> bool {
+        true
+    }
+}
+
+#[tokio::test]
+async fn context_is_read_for_methods_that_report_it() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    server.ledger().advance_slots(4);
+
+    let balance = client
+        .call(RpcGetBalanceRequest {
+            pubkey: "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM".to_string(),
+            config: None,
+        })
+        .await
+        .unwrap();
+    let slot = client
+        .call(RpcGetSlotRequest {
+            commitment: None,
+            min_context_slot: None,
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(balance.slot(), Some(4));
+    assert_eq!(slot.slot(), None);
+    assert_eq!(slot.value, 4);
+}
+
+#[tokio::test]
+async fn program_accounts_follow_with_context() {
+    let server = MockServer::start().await;
+    let client = server.client();
+    let program_id = "BPFLoaderUpgradeab1e11111111111111111111111";
+    server
+        .ledger()
+        .set_account("account", MockAccount::system(1).with_data(program_id, vec![1, 2, 3]));
+    server.ledger().advance_slots(2);
+
+    for with_context in [None, Some(false), Some(true)] {
+        let accounts = client
+            .call(RpcGetProgramAccountsRequest {
+                program_id: program_id.to_string(),
+                config: Some(ProgramAccountsConfig {
+                    with_context,
+                    ..Default::default()
+                }),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(accounts.value.len(), 1);
+        assert_eq!(accounts.slot(), with_context.unwrap_or(false).then_some(2));
+    }
+}
+
+#[tokio::test]
+async fn requests_defined_outside_the_crate_choose_their_shape() {
+    let server = MockServer::start().await;
+    server.respond_with("getFeeForMessage", |_| {
+        Ok(json!({ "context": { "slot": 9 }, "value": 5000 }))
+    });
+
+    let fee = server
+        .client()
+        .call(GetFeeForMessageRequest(vec!["message".to_string()]))
+        .await
+        .unwrap();
+
+    assert_eq!(fee.slot(), Some(9));
+    assert_eq!(fee.value, Some(5000));
+}
@@ -0,0 +1,76 @@
+use futures::StreamExt;
+use solana_jsonrpc_client::methods::program::{ProgramAccountsConfig, RpcGetProgramAccountsRequest};
+use solana_jsonrpc_client::test_utils::{Fault, MockAccount, MockServer};
+use solana_jsonrpc_client::JsonRpcError;
+
+const PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+async fn server_with_accounts(count: usize) -> MockServer {
+    let server = MockServer::start().await;
+    for i in 0..count {
+        server.ledger().set_account(
+            format!("account-{}", i),
+            MockAccount::system(i as u64).with_data(PROGRAM, vec![i as u8; 64]),
+        );
+    }
+    server
+}
+
+fn request(with_context: Option<bool>) -> RpcGetProgramAccountsRequest {
+    RpcGetProgramAccountsRequest {
+        program_id: PROGRAM.to_string(),
+        config: Some(ProgramAccountsConfig {
+            with_context,
+            ..Default::default()
+        }),
+    }
+}
+
+#[tokio::test]
+async fn streams_every_account() {
+    let server = server_with_accounts(50).await;
+
+    for with_context in [None, Some(true)] {
+        let mut accounts: Vec<_> = server
+            .client()
+            .stream_program_accounts(request(with_context))
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        accounts.sort_by_key(|account| account.account.lamports);
+
+        assert_eq!(accounts.len(), 50);
+        assert_eq!(accounts[7].pubkey, "account-7");
+    }
+}
+
+#[tokio::test]
+async fn ignores_the_max_response_size() {
+    let server = server_with_accounts(50).await;
+    let client = server.client().with_max_response_size(1024);
+
+    let err = client.call(request(None)).await.unwrap_err();
+    assert!(matches!(err, JsonRpcError::ResponseTooLarge { limit: 1024 }));
+
+    let streamed = client.stream_program_accounts(request(None)).await.unwrap().count().await;
+    assert_eq!(streamed, 50);
+}
+
+#[tokio::test]
+async fn ends_with_the_rpc_error() {
+    let server = server_with_accounts(1).await;
+    server.inject_fault("getProgramAccounts", Fault::rpc(-32010, "excluded from secondary indexes"));
+
+    let items: Vec<_> = server
+        .client()
+        .stream_program_accounts(request(None))
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(items.len(), 1);
+    assert!(matches!(items[0], Err(JsonRpcError::Rpc { code: -32010, .. })));
+}